@@ -0,0 +1,42 @@
+/*
+ * C bindings of cradle_system, build the library with
+ * `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`).
+ * Kept in sync with src/ffi.rs by its tests.
+ */
+#ifndef CRADLE_SYSTEM_H
+#define CRADLE_SYSTEM_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An opaque cradle, created by cradle_new and released by cradle_stop. */
+typedef struct Cradle Cradle;
+
+/* Cry callback: receives userdata and the elapsed seconds, returns 0 on success. */
+typedef int32_t (*CryFn)(void *userdata, size_t elapsed);
+
+/* Instantiates a new empty cradle. */
+Cradle *cradle_new(void);
+
+/* Puts a baby into the cradle, returns its id, or SIZE_MAX on failure.
+ * userdata must stay valid, and usable from another thread, until cradle_stop. */
+size_t cradle_put_baby(Cradle *cradle, CryFn cry, void *userdata);
+
+/* Starts the cradle, returns 0 on success. */
+int32_t cradle_start(Cradle *cradle);
+
+/* Resets the cradle's elapsed time, returns 0 on success. */
+int32_t cradle_reset(Cradle *cradle);
+
+/* Gracefully stops and frees the cradle, returns 0 on clean exit, -1 if a cry failed. */
+int32_t cradle_stop(Cradle *cradle);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CRADLE_SYSTEM_H */
@@ -0,0 +1,178 @@
+//! C ABI bindings, enabled by the `ffi` feature.
+//!
+//! The matching header lives in `include/cradle_system.h`, build the C library with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`).
+//!
+//! A cradle created by [`cradle_new`] must be released by [`cradle_stop`] exactly once.
+
+use crate::local::{Baby, BabyId, BoxResult, Cradle};
+use std::{
+    ffi::c_void,
+    fmt,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+/// The cry callback of a C baby.
+///
+/// It receives the userdata given to [`cradle_put_baby`] and the elapsed time in seconds,
+/// and should return 0 on success. Any other value stops the cradle with an error.
+pub type CryFn = extern "C" fn(userdata: *mut c_void, elapsed: usize) -> i32;
+
+struct CBaby {
+    cry: CryFn,
+    userdata: *mut c_void,
+}
+
+// SAFETY: the caller of `cradle_put_baby` guarantees `userdata` may be used from the cradle thread.
+unsafe impl Send for CBaby {}
+
+impl Baby for CBaby {
+    fn cry(&mut self, elapsed: usize) -> BoxResult<()> {
+        match (self.cry)(self.userdata, elapsed) {
+            0 => Ok(()),
+            code => Err(Box::new(CryError(code))),
+        }
+    }
+}
+
+/// The error returned by a C cry callback.
+#[derive(Debug)]
+struct CryError(i32);
+
+impl fmt::Display for CryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cry callback returned {}", self.0)
+    }
+}
+
+impl std::error::Error for CryError {}
+
+/// Instantiates a new empty cradle.
+#[no_mangle]
+pub extern "C" fn cradle_new() -> *mut Cradle {
+    Box::into_raw(Box::new(Cradle::new(Vec::<CBaby>::new())))
+}
+
+/// Puts a baby crying through `cry` into the cradle, returning its id.
+///
+/// # Safety
+/// `cradle` must come from [`cradle_new`] and not be stopped yet,
+/// `userdata` must stay valid and usable from another thread until the cradle is stopped.
+#[no_mangle]
+pub unsafe extern "C" fn cradle_put_baby(
+    cradle: *mut Cradle,
+    cry: CryFn,
+    userdata: *mut c_void,
+) -> BabyId {
+    let cradle = &*cradle;
+    catch_unwind(AssertUnwindSafe(|| {
+        cradle.put_baby(CBaby { cry, userdata })
+    }))
+    .unwrap_or(BabyId::MAX)
+}
+
+/// Starts the cradle, returns 0 on success.
+///
+/// # Safety
+/// `cradle` must come from [`cradle_new`] and not be stopped yet.
+#[no_mangle]
+pub unsafe extern "C" fn cradle_start(cradle: *mut Cradle) -> i32 {
+    let cradle = &*cradle;
+    status(catch_unwind(AssertUnwindSafe(|| cradle.start())).is_ok())
+}
+
+/// Resets the cradle's elapsed time, returns 0 on success.
+///
+/// # Safety
+/// `cradle` must come from [`cradle_new`] and not be stopped yet.
+#[no_mangle]
+pub unsafe extern "C" fn cradle_reset(cradle: *mut Cradle) -> i32 {
+    let cradle = &*cradle;
+    status(catch_unwind(AssertUnwindSafe(|| cradle.reset())).is_ok())
+}
+
+/// Gracefully stops the cradle, waits for its thread and frees it.
+///
+/// Returns 0 if the cradle exited cleanly, -1 if a cry failed.
+///
+/// # Safety
+/// `cradle` must come from [`cradle_new`], it is invalid after this call.
+#[no_mangle]
+pub unsafe extern "C" fn cradle_stop(cradle: *mut Cradle) -> i32 {
+    let cradle = Box::from_raw(cradle);
+    status(
+        catch_unwind(AssertUnwindSafe(move || {
+            // The thread has already exited if a cry failed.
+            let _ = catch_unwind(AssertUnwindSafe(|| cradle.stop()));
+            matches!(cradle.join(), Ok(Ok(())))
+        }))
+        .unwrap_or(false),
+    )
+}
+
+fn status(ok: bool) -> i32 {
+    if ok {
+        0
+    } else {
+        -1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+        time::Duration,
+    };
+
+    extern "C" fn count(userdata: *mut c_void, _elapsed: usize) -> i32 {
+        let count = unsafe { &*(userdata as *const AtomicUsize) };
+        count.fetch_add(1, Ordering::Relaxed);
+        0
+    }
+
+    #[test]
+    fn test_ffi() {
+        let times = AtomicUsize::new(0);
+        unsafe {
+            let cradle = cradle_new();
+            let userdata = &times as *const AtomicUsize as *mut c_void;
+            assert_eq!(cradle_put_baby(cradle, count, userdata), 0);
+            assert_eq!(cradle_start(cradle), 0);
+            thread::sleep(Duration::from_millis(1500));
+            assert_eq!(cradle_reset(cradle), 0);
+            assert_eq!(cradle_stop(cradle), 0);
+        }
+        assert!(times.load(Ordering::Relaxed) >= 2);
+    }
+
+    #[test]
+    fn test_header() {
+        // Each function must keep the signature declared in the header.
+        let _: extern "C" fn() -> *mut Cradle = cradle_new;
+        let _: unsafe extern "C" fn(*mut Cradle, CryFn, *mut c_void) -> BabyId = cradle_put_baby;
+        let _: unsafe extern "C" fn(*mut Cradle) -> i32 = cradle_start;
+        let _: unsafe extern "C" fn(*mut Cradle) -> i32 = cradle_reset;
+        let _: unsafe extern "C" fn(*mut Cradle) -> i32 = cradle_stop;
+        let header = include_str!("../include/cradle_system.h");
+        let declarations = [
+            "typedef int32_t (*CryFn)(void *userdata, size_t elapsed);",
+            "Cradle *cradle_new(void);",
+            "size_t cradle_put_baby(Cradle *cradle, CryFn cry, void *userdata);",
+            "int32_t cradle_start(Cradle *cradle);",
+            "int32_t cradle_reset(Cradle *cradle);",
+            "int32_t cradle_stop(Cradle *cradle);",
+        ];
+        for declaration in declarations {
+            assert!(header.contains(declaration), "{declaration}");
+        }
+        // No function is declared without being checked above.
+        let functions = header
+            .lines()
+            .filter(|line| line.contains("cradle_") && line.ends_with(");"))
+            .count();
+        assert_eq!(functions, declarations.len() - 1);
+    }
+}
@@ -1,5 +1,7 @@
 #![doc = include_str!("../README.md")]
 #![deny(missing_docs)]
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod local;
 pub mod remote;
@@ -0,0 +1,282 @@
+use super::{
+    worker::Worker, Baby, BabyId, BoxResult, Cradle, CradleObserver, CryContext, ErrorSource,
+    Health, Resets, Severity, Status,
+};
+use std::{
+    error::Error,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize},
+        mpsc::channel,
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+/// A builder to configure a [`Cradle`].
+#[derive(Default)]
+pub struct CradleBuilder {
+    pub(super) babies: Vec<Box<dyn Baby + Send>>,
+    pub(super) align: Option<Duration>,
+    pub(super) rate_limit: Option<(usize, Digest)>,
+    pub(super) dedup: Option<Duration>,
+    pub(super) consecutive: usize,
+    pub(super) severity_actions: Vec<(Severity, Action)>,
+    pub(super) default_cry: Option<Action>,
+    pub(super) batch: Option<(usize, Batch)>,
+    pub(super) health_report: Option<(Duration, HealthReport)>,
+    pub(super) silent: bool,
+    pub(super) farewell: Option<Farewell>,
+    pub(super) on_error: Option<ErrorHandler>,
+    pub(super) ticker: Option<(Duration, Vec<BabyId>)>,
+    pub(super) state_events: bool,
+    pub(super) shards: Option<usize>,
+    pub(super) cry_budget: Option<(Duration, usize, Duration)>,
+    pub(super) grace: Duration,
+    #[cfg(feature = "chaos")]
+    pub(super) chaos: super::Chaos,
+    auto_start: bool,
+}
+
+/// The farewell action of [`CradleBuilder::farewell`].
+pub(super) type Farewell = Box<dyn FnMut() -> BoxResult<()> + Send>;
+
+/// The batch handler of [`CradleBuilder::batch`].
+pub(super) type Batch = Box<dyn FnMut(&[CryContext]) -> BoxResult<()> + Send>;
+
+/// The handler of [`CradleBuilder::health_report`].
+pub(super) type HealthReport = Box<dyn FnMut(&Health) -> BoxResult<()> + Send>;
+
+/// The error handler of [`CradleBuilder::on_error`].
+pub(super) type ErrorHandler = Box<dyn FnMut(&dyn Error, ErrorSource) + Send>;
+
+/// A cradle-level cry action.
+pub(super) type Action = Box<dyn FnMut(&CryContext) -> BoxResult<()> + Send>;
+
+/// The digest cry of [`CradleBuilder::rate_limit`].
+pub(super) type Digest = Box<dyn FnMut(usize) -> BoxResult<()> + Send>;
+
+impl CradleBuilder {
+    /// Puts a baby into the cradle to build, babies are numbered from 0 in order.
+    pub fn baby<B>(mut self, baby: B) -> Self
+    where
+        B: Baby + Send + 'static,
+    {
+        self.babies.push(Box::new(baby));
+        self
+    }
+
+    /// Coalesces wakeups to wall-clock multiples of `boundary`, rounded to whole seconds.
+    ///
+    /// Instead of waking up every second, the cradle only rocks babies on these boundaries,
+    /// so their elapsed time grows by up to `boundary` between two cries.
+    /// Useful for battery devices that should not wake the CPU unnecessarily.
+    pub fn align(mut self, boundary: Duration) -> Self {
+        self.align = Some(boundary);
+        self
+    }
+
+    /// Limits cries across all babies to `max` per minute.
+    ///
+    /// Cries over the limit are dropped, and summarized into a single call to `digest`
+    /// with the number of dropped cries once the minute is over.
+    /// Babies whose cries were dropped are served first afterwards, so that none starves.
+    pub fn rate_limit<F>(mut self, max: usize, digest: F) -> Self
+    where
+        F: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        self.rate_limit = Some((max, Box::new(digest)));
+        self
+    }
+
+    /// Collapses cries of the same baby, or babies with the same [`Baby::dedup_key`],
+    /// within `window` into one.
+    ///
+    /// The first cry goes through, the following ones within `window` are dropped,
+    /// and the next cry after it reports how many were dropped via [`Baby::cry_repeated`].
+    /// Dropped cries not followed by another within `window` are forgotten.
+    pub fn dedup(mut self, window: Duration) -> Self {
+        self.dedup = Some(window);
+        self
+    }
+
+    /// Requires a baby to want to cry, see [`Baby::should_cry`],
+    /// on `ticks` consecutive ticks before it actually cries.
+    ///
+    /// This filters out single-tick blips, e.g. caused by scheduler hiccups.
+    pub fn consecutive(mut self, ticks: usize) -> Self {
+        self.consecutive = ticks;
+        self
+    }
+
+    /// Adds a default action for cries of `severity`, called in addition to the baby's own cry.
+    ///
+    /// Several actions may be added for the same severity, they run in order.
+    pub fn on_severity<F>(mut self, severity: Severity, action: F) -> Self
+    where
+        F: FnMut(&CryContext) -> BoxResult<()> + Send + 'static,
+    {
+        self.severity_actions.push((severity, Box::new(action)));
+        self
+    }
+
+    /// Sets the cry of babies without one of their own, see [`Baby::has_cry`].
+    ///
+    /// Without it, such babies crying with no [`CradleBuilder::on_severity`] action for them
+    /// fail with an error, handed to [`CradleBuilder::on_error`], rather than going unheard.
+    pub fn default_cry<F>(mut self, action: F) -> Self
+    where
+        F: FnMut(&CryContext) -> BoxResult<()> + Send + 'static,
+    {
+        self.default_cry = Some(Box::new(action));
+        self
+    }
+
+    /// Once a tick finds at least `min` babies crying, calls `handler` once with all of them
+    /// instead of their individual cries and cradle-level actions,
+    /// e.g. for a single "7 services are overdue" notification.
+    ///
+    /// A `min` of 0 counts as 1, the handler is never called with no cry.
+    /// [`Severity::Critical`] cries are never batched.
+    pub fn batch<F>(mut self, min: usize, handler: F) -> Self
+    where
+        F: FnMut(&[CryContext]) -> BoxResult<()> + Send + 'static,
+    {
+        self.batch = Some((min, Box::new(handler)));
+        self
+    }
+
+    /// Reports the cradle's [`Health`] to `handler` every `period`, rounded up to whole ticks,
+    /// independently of individual cries.
+    pub fn health_report<F>(mut self, period: Duration, handler: F) -> Self
+    where
+        F: FnMut(&Health) -> BoxResult<()> + Send + 'static,
+    {
+        self.health_report = Some((period, Box::new(handler)));
+        self
+    }
+
+    /// Records cries without making any: babies are evaluated as usual,
+    /// but each cry becomes an [`Event::WouldCry`](super::Event::WouldCry) and no action runs,
+    /// neither cries, cradle-level actions nor goodbyes. Health reports still run.
+    ///
+    /// Useful to shadow-run a new configuration before trusting it.
+    pub fn silent(mut self) -> Self {
+        self.silent = true;
+        self
+    }
+
+    /// Sets an action called once when the started cradle is gracefully stopped,
+    /// after every baby's [`Baby::on_shutdown`].
+    pub fn farewell<F>(mut self, action: F) -> Self
+    where
+        F: FnMut() -> BoxResult<()> + Send + 'static,
+    {
+        self.farewell = Some(Box::new(action));
+        self
+    }
+
+    /// Sets a handler for every error in the cradle, such as failed cries.
+    ///
+    /// Without a handler, the first failure stops the cradle and is returned by [`Cradle::join`].
+    /// With one, errors are handed to it and the cradle keeps rocking.
+    pub fn on_error<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(&dyn Error, ErrorSource) + Send + 'static,
+    {
+        self.on_error = Some(Box::new(handler));
+        self
+    }
+
+    /// Emits an [`Event::Countdown`](super::Event::Countdown) for each of `babies`
+    /// with a [`Baby::timeout`] every `every`, rounded up to whole ticks.
+    ///
+    /// Meant for UIs rendering live countdowns without polling the cradle.
+    pub fn ticker<I>(mut self, every: Duration, babies: I) -> Self
+    where
+        I: IntoIterator<Item = BabyId>,
+    {
+        self.ticker = Some((every, babies.into_iter().collect()));
+        self
+    }
+
+    /// Emits an [`Event::StateChanged`](super::Event::StateChanged) each time a baby changes
+    /// [`BabyState`](super::BabyState), with the reason why.
+    ///
+    /// Meant for automation branching on exact transitions rather than inferring them from cries.
+    pub fn state_events(mut self) -> Self {
+        self.state_events = true;
+        self
+    }
+
+    /// Spreads babies over `shards` shards, only one of which is rocked per tick in turn,
+    /// smoothing the work of cradles holding many babies.
+    ///
+    /// Babies are assigned shards by [`BabyId`] unless [`Baby::shard`] is given,
+    /// so their cries, as well as their [`Baby::ttl`] expiry and unmuting, come up to `shards` ticks late.
+    /// Critical babies are rocked every tick.
+    pub fn shards(mut self, shards: usize) -> Self {
+        self.shards = Some(shards);
+        self
+    }
+
+    /// Mutes a baby for `mute` once its cries took longer than `budget` `strikes` times in a row,
+    /// with an [`Event::OverBudget`](super::Event::OverBudget), protecting the cradle from slow actions.
+    ///
+    /// A cry's time includes its cradle-level actions, see [`Cradle::cry_time`].
+    pub fn cry_budget(mut self, budget: Duration, strikes: usize, mute: Duration) -> Self {
+        self.cry_budget = Some((budget, strikes, mute));
+        self
+    }
+
+    /// Keeps every baby from crying during `grace` after [`Cradle::start`], whatever its deadline,
+    /// so that services with slow initialization do not trigger a storm of cries
+    /// before their heartbeats begin.
+    ///
+    /// Babies still age meanwhile, [`Cradle::cry`] goes through.
+    /// Deadlines, see [`Cradle::next_deadline`] and [`Cradle::predict`], fall after the grace period.
+    pub fn grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self
+    }
+
+    /// Starts the cradle as soon as it holds a baby,
+    /// either given to this builder or later via [`Cradle::put_baby`].
+    pub fn auto_start(mut self) -> Self {
+        self.auto_start = true;
+        self
+    }
+
+    /// Builds the cradle, which spawns its thread and waits for [`Cradle::start`].
+    pub fn build(self) -> Cradle {
+        let next_id = AtomicUsize::new(self.babies.len());
+        let auto_start = self.auto_start;
+        let start = auto_start && !self.babies.is_empty();
+        let (tx, rx) = channel();
+        let status = Arc::new(Mutex::new(Status::default()));
+        let resets = Arc::new(Resets::new());
+        #[cfg(feature = "chaos")]
+        let chaos = self.chaos.clone();
+        let worker = Worker::new(rx, self, status.clone(), resets.clone());
+        let jh = thread::spawn(move || worker.run());
+        let observer = CradleObserver {
+            tx: tx.clone(),
+            status,
+            resets,
+        };
+        let cradle = Cradle {
+            tx,
+            jh,
+            next_id,
+            started: AtomicBool::new(false),
+            auto_start,
+            observer,
+            #[cfg(feature = "chaos")]
+            chaos,
+        };
+        if start {
+            cradle.start();
+        }
+        cradle
+    }
+}
@@ -0,0 +1,86 @@
+use super::Cradle;
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Fault injection into a cradle, controlled at runtime, see [`Cradle::chaos`].
+///
+/// Meant to verify an alerting pipeline end-to-end by letting babies cry on purpose.
+/// Clones control the same cradle.
+#[derive(Clone, Default)]
+pub struct Chaos {
+    state: Arc<ChaosState>,
+}
+
+#[derive(Default)]
+struct ChaosState {
+    /// Drops every nth reset, 0 for none.
+    drop_every: AtomicUsize,
+    /// Resets seen so far.
+    resets: AtomicUsize,
+    /// Delay before each cry, in nanoseconds.
+    delay: AtomicU64,
+    /// Pending clock jump, in nanoseconds.
+    jump: AtomicU64,
+}
+
+impl Chaos {
+    /// Drops every `n`th reset, from [`Cradle::reset`], [`Cradle::reset_baby`] and heartbeats alike,
+    /// 0 for none.
+    pub fn drop_resets(&self, n: usize) {
+        self.state.drop_every.store(n, Ordering::Relaxed);
+        self.state.resets.store(0, Ordering::Relaxed);
+    }
+
+    /// Delays each cry by `delay`, zero for none.
+    pub fn delay_cries(&self, delay: Duration) {
+        let nanos = delay.as_nanos() as u64;
+        self.state.delay.store(nanos, Ordering::Relaxed);
+    }
+
+    /// Makes the clock jump forward by `by` on the next tick, aging every baby as much.
+    ///
+    /// The jump moves what the cradle keeps per baby: since when it waits, its
+    /// [`Baby::ttl`](super::Baby::ttl), mute, [`Baby::rate`](super::Baby::rate) resets and
+    /// [`Baby::sources`](super::Baby::sources) check-ins, as well as the
+    /// [`CradleBuilder::grace`](super::CradleBuilder::grace) period.
+    /// Cradle-wide windows, e.g. the rate limit, dedup and health report, keep the real clock.
+    pub fn jump_clock(&self, by: Duration) {
+        let nanos = by.as_nanos() as u64;
+        self.state.jump.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    /// Stops injecting faults, dropping a pending clock jump.
+    pub fn clear(&self) {
+        self.drop_resets(0);
+        self.delay_cries(Duration::ZERO);
+        self.state.jump.store(0, Ordering::Relaxed);
+    }
+
+    /// Whether to drop the reset being made.
+    pub(super) fn drops_reset(&self) -> bool {
+        let every = self.state.drop_every.load(Ordering::Relaxed);
+        every > 0 && (self.state.resets.fetch_add(1, Ordering::Relaxed) + 1).is_multiple_of(every)
+    }
+
+    /// The delay before each cry.
+    pub(super) fn cry_delay(&self) -> Duration {
+        Duration::from_nanos(self.state.delay.load(Ordering::Relaxed))
+    }
+
+    /// Takes the pending clock jump.
+    pub(super) fn take_jump(&self) -> Duration {
+        Duration::from_nanos(self.state.jump.swap(0, Ordering::Relaxed))
+    }
+}
+
+impl Cradle {
+    /// A handle injecting faults into the cradle.
+    pub fn chaos(&self) -> Chaos {
+        self.chaos.clone()
+    }
+}
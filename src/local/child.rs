@@ -0,0 +1,50 @@
+use super::{heartbeat::Cry, Baby, BabyId, BoxResult, Cradle, Status};
+use std::sync::{Arc, Mutex};
+
+/// A baby watching another cradle, see [`Cradle::adopt`].
+///
+/// It cries once the child cradle has missed two ticks, unless it was gracefully stopped.
+pub struct Child {
+    status: Arc<Mutex<Status>>,
+    cry: Cry,
+}
+
+impl Child {
+    /// Watches `child`, calling `cry` with the elapsed time once it is missed.
+    pub fn new<C>(child: &Cradle, cry: C) -> Self
+    where
+        C: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        Self {
+            status: child.observer.status.clone(),
+            cry: Box::new(cry),
+        }
+    }
+}
+
+impl Baby for Child {
+    fn cry(&mut self, elapsed: usize) -> BoxResult<()> {
+        (self.cry)(elapsed)
+    }
+
+    fn should_cry(&self, _elapsed: usize) -> bool {
+        let status = Status::lock(&self.status);
+        !status.stopped
+            && status
+                .last_tick
+                .is_some_and(|at| at.elapsed() > status.period * 2)
+    }
+}
+
+impl Cradle {
+    /// Puts a [`Child`] baby watching `child` into this cradle, returning its id.
+    ///
+    /// This cradle calls `cry` once the child's thread stops rocking its babies,
+    /// e.g. because a cry failed or blocked, which is the building block of supervision trees.
+    pub fn adopt<C>(&self, child: &Cradle, cry: C) -> BabyId
+    where
+        C: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        self.put_baby(Child::new(child, cry))
+    }
+}
@@ -0,0 +1,90 @@
+use super::{worker::Worker, Baby, BabyId, BoxResult, CradleBuilder, Resets, Signal, Status};
+use std::{
+    sync::{mpsc::channel, Arc, Mutex},
+    time::Instant,
+};
+
+/// A command applied to a [`CradleCore`].
+#[non_exhaustive]
+pub enum Command {
+    /// Puts a baby in, numbered after the builder's like [`Cradle::put_baby`](super::Cradle::put_baby).
+    Put(Box<dyn Baby + Send>),
+    /// Resets every baby's elapsed time, see [`Cradle::reset`](super::Cradle::reset).
+    Reset,
+    /// Resets a single baby's elapsed time, unknown ids are ignored.
+    ResetBaby(BabyId),
+    /// Removes a baby, unknown ids are ignored.
+    Remove(BabyId),
+    /// Mutes every baby until [`Command::Unmute`].
+    Mute,
+    /// Unmutes every baby.
+    Unmute,
+    /// Makes a baby cry right away, see [`Cradle::cry`](super::Cradle::cry).
+    Cry(BabyId),
+    /// Rocks the babies due on a tick, as the cradle thread does once per second.
+    Rock,
+}
+
+/// The rules of a [`Cradle`](super::Cradle) as a deterministic state machine,
+/// see [`CradleBuilder::build_core`].
+///
+/// It runs the very code of the cradle thread, but without thread nor channel:
+/// time only moves through the `now` given to [`CradleCore::apply`], and babies are only rocked
+/// on [`Command::Rock`], so that a sequence of commands always has the same outcome,
+/// e.g. to property-test that a baby never cries before its [`Baby::timeout`].
+/// Only [`CradleBuilder::cry_budget`] still times cries with the real clock.
+pub struct CradleCore {
+    worker: Worker,
+    next_id: BabyId,
+}
+
+impl CradleCore {
+    /// Applies a single command at `now`, returning the babies which cried, in order.
+    ///
+    /// The first command starts the cradle at its `now`, and `now` going backwards
+    /// counts as no time passing. Errors are handled as by the cradle thread:
+    /// handed to [`CradleBuilder::on_error`] if any, otherwise returned.
+    pub fn apply(&mut self, command: Command, now: Instant) -> BoxResult<Vec<BabyId>> {
+        if !self.worker.started() {
+            self.worker.start(now);
+        }
+        let result = match command {
+            Command::Put(baby) => {
+                let id = self.next_id;
+                self.next_id += 1;
+                self.worker.handle(Signal::Put(id, baby), now)
+            }
+            Command::Reset => {
+                self.worker.reset_all(now, 1);
+                Ok(())
+            }
+            Command::ResetBaby(id) => self.worker.handle(Signal::ResetBaby(id), now),
+            Command::Remove(id) => self.worker.handle(Signal::Remove(id), now),
+            Command::Mute => self.worker.handle(Signal::Mute, now),
+            Command::Unmute => self.worker.handle(Signal::Unmute, now),
+            Command::Cry(id) => self.worker.handle(Signal::Cry(id), now),
+            Command::Rock => self.worker.rock(now),
+        };
+        let cried = self.worker.take_cried();
+        result.map(|()| cried)
+    }
+
+    /// The babies' ids, in order.
+    pub fn ids(&self) -> Vec<BabyId> {
+        self.worker.ids()
+    }
+}
+
+impl CradleBuilder {
+    /// Builds a [`CradleCore`] following the rules configured so far, instead of a cradle.
+    ///
+    /// [`CradleBuilder::auto_start`] and [`CradleBuilder::align`] do not apply.
+    pub fn build_core(self) -> CradleCore {
+        let next_id = self.babies.len();
+        let (_, rx) = channel();
+        let status = Arc::new(Mutex::new(Status::default()));
+        let mut worker = Worker::new(rx, self, status, Arc::new(Resets::new()));
+        worker.record_cries();
+        CradleCore { worker, next_id }
+    }
+}
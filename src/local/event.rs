@@ -0,0 +1,127 @@
+use super::{BabyId, ErrorSource, Metadata};
+use std::{any::Any, fmt, sync::Arc, time::Duration};
+
+/// Something that happened in the cradle, see [`Cradle::subscribe`](super::Cradle::subscribe).
+///
+/// Events name babies by id, [`Event::WouldCry`] and [`Event::StateChanged`] also carry
+/// their [`Baby::metadata`](super::Baby::metadata), e.g. to route them without looking the ids up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Event {
+    /// The baby's mute expired, so it may cry again.
+    Unmuted(BabyId),
+    /// The baby's [`Baby::ttl`](super::Baby::ttl) is over, it has been removed from the cradle.
+    Expired(BabyId),
+    /// The time remaining before the baby is due to cry, see [`CradleBuilder::ticker`](super::CradleBuilder::ticker).
+    Countdown {
+        /// The baby counting down.
+        id: BabyId,
+        /// The time remaining, zero if the baby is overdue.
+        remaining: Duration,
+    },
+    /// The baby would have cried, but the cradle is [`silent`](super::CradleBuilder::silent)
+    /// or [`muted`](super::Cradle::mute).
+    WouldCry {
+        /// The baby.
+        id: BabyId,
+        /// Its elapsed time in seconds.
+        elapsed: usize,
+        /// Its metadata, as a cry would have carried.
+        metadata: Option<EventMetadata>,
+    },
+    /// The baby's cries took too long, it has been muted, see [`CradleBuilder::cry_budget`](super::CradleBuilder::cry_budget).
+    OverBudget(BabyId),
+    /// The baby went from one state to another, see [`CradleBuilder::state_events`](super::CradleBuilder::state_events).
+    StateChanged {
+        /// The baby.
+        id: BabyId,
+        /// Its previous state.
+        from: BabyState,
+        /// Its new state.
+        to: BabyState,
+        /// Why it changed.
+        reason: Reason,
+        /// Its metadata.
+        metadata: Option<EventMetadata>,
+    },
+    /// A cry, cradle-level action or other callback of the cradle panicked, from the given source.
+    ///
+    /// The panic was caught and handed to [`CradleBuilder::on_error`](super::CradleBuilder::on_error)
+    /// as an error, so the cradle keeps rocking with a handler, and stops cleanly without.
+    Panicked(ErrorSource),
+    /// A thread panicked while holding the lock shared by the cradle and its handles.
+    ///
+    /// The lock was recovered, what it guards staying consistent, so neither the cradle
+    /// nor its handles panic in turn.
+    RegistryPoisoned,
+}
+
+/// A baby's [`Metadata`] carried by an [`Event`].
+///
+/// Two of them are equal when they share the same allocation, so that events stay comparable.
+#[derive(Clone)]
+pub struct EventMetadata(pub Metadata);
+
+impl EventMetadata {
+    /// The metadata as a `T`, if it is one.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+impl From<Metadata> for EventMetadata {
+    fn from(metadata: Metadata) -> Self {
+        Self(metadata)
+    }
+}
+
+impl PartialEq for EventMetadata {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for EventMetadata {}
+
+impl fmt::Debug for EventMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EventMetadata").finish_non_exhaustive()
+    }
+}
+
+/// Where a baby stands, see [`Event::StateChanged`].
+///
+/// Babies go from [`BabyState::Sleeping`] to [`BabyState::Late`] to [`BabyState::Crying`],
+/// then back through [`BabyState::Soothed`] to [`BabyState::Sleeping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BabyState {
+    /// The baby does not want to cry.
+    Sleeping,
+    /// The baby wants to cry, but has not yet, e.g. held back by
+    /// [`CradleBuilder::consecutive`](super::CradleBuilder::consecutive) or a mute.
+    Late,
+    /// The baby cried.
+    Crying,
+    /// The baby stopped wanting to cry, and sleeps again on the next tick it stays so.
+    Soothed,
+}
+
+/// Why a baby changed state, see [`Event::StateChanged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Reason {
+    /// The baby wants to cry, its [`Baby::timeout`](super::Baby::timeout) elapsed
+    /// or its other criteria failed.
+    TimeoutElapsed,
+    /// The baby was made to cry by [`Cradle::cry`](super::Cradle::cry).
+    ManualCry,
+    /// The baby, or the whole cradle, was reset.
+    ResetReceived,
+    /// The baby, or the whole cradle, is muted, holding its cries back.
+    Muted,
+    /// The baby no longer wants to cry on its own, e.g. a banked reset was spent.
+    Recovered,
+    /// The cradle is within its [`CradleBuilder::grace`](super::CradleBuilder::grace) period,
+    /// holding every cry back.
+    Grace,
+}
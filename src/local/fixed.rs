@@ -0,0 +1,58 @@
+use super::{worker::Unheard, Baby, BabyId, BoxResult};
+use std::time::Instant;
+
+/// A cradle holding a fixed array of babies, without any heap allocation of its own.
+///
+/// It has no thread either: babies are only rocked by [`StaticCradle::rock`],
+/// e.g. from the main loop, each crying once [`Baby::should_cry`] since it was last reset.
+/// Babies are numbered by their index in the array.
+pub struct StaticCradle<B, const N: usize> {
+    babies: [B; N],
+    since: [Instant; N],
+}
+
+impl<B: Baby, const N: usize> StaticCradle<B, N> {
+    /// Instantiates a new cradle, the babies' elapsed time starts from 0.
+    pub fn new(babies: [B; N]) -> Self {
+        Self {
+            babies,
+            since: [Instant::now(); N],
+        }
+    }
+
+    /// Resets the cradle's elapsed time, so that babies will not cry.
+    pub fn reset(&mut self) {
+        self.since = [Instant::now(); N];
+    }
+
+    /// Resets a single baby's elapsed time, unknown ids are ignored.
+    pub fn reset_baby(&mut self, id: BabyId) {
+        if let Some(since) = self.since.get_mut(id) {
+            *since = Instant::now();
+        }
+    }
+
+    /// Rocks each baby once, in order, stopping at the first error.
+    ///
+    /// Having no [`CradleBuilder::default_cry`](super::CradleBuilder::default_cry),
+    /// a baby without a cry of its own, see [`Baby::has_cry`], fails as soon as it cries.
+    pub fn rock(&mut self) -> BoxResult<()> {
+        let now = Instant::now();
+        for (id, (baby, since)) in self.babies.iter_mut().zip(&self.since).enumerate() {
+            let elapsed = now.saturating_duration_since(*since).as_secs() as usize;
+            if !baby.should_cry(elapsed) {
+                continue;
+            }
+            if !baby.has_cry() {
+                return Err(Box::new(Unheard(id)));
+            }
+            baby.cry(elapsed)?;
+        }
+        Ok(())
+    }
+
+    /// The babies, in order.
+    pub fn babies(&self) -> &[B; N] {
+        &self.babies
+    }
+}
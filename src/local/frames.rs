@@ -0,0 +1,123 @@
+use super::{heartbeat::Cry, Baby, BoxResult, Cradle};
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// A handle for an event loop to report its frames, see [`Cradle::frames`].
+#[derive(Clone)]
+pub struct Frames {
+    state: Arc<Mutex<FrameState>>,
+}
+
+/// Statistics on frame intervals, see [`Frames::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FrameStats {
+    /// How many frames have been reported.
+    pub frames: usize,
+    /// The mean interval between two frames.
+    pub mean: Duration,
+    /// The longest interval between two frames.
+    pub max: Duration,
+    /// The standard deviation of intervals.
+    pub jitter: Duration,
+}
+
+struct FrameState {
+    threshold: Duration,
+    last: Instant,
+    slow: usize,
+    frames: usize,
+    sum: f64,
+    sum_sq: f64,
+    max: Duration,
+}
+
+impl Frames {
+    /// Reports a frame, cheap enough to be called on each loop iteration.
+    pub fn frame(&self) {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let interval = now - state.last;
+        state.last = now;
+        state.frames += 1;
+        state.sum += interval.as_secs_f64();
+        state.sum_sq += interval.as_secs_f64().powi(2);
+        state.max = state.max.max(interval);
+        state.slow = if interval > state.threshold {
+            state.slow + 1
+        } else {
+            0
+        };
+    }
+
+    /// Statistics on the intervals reported so far.
+    pub fn stats(&self) -> FrameStats {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.frames == 0 {
+            return FrameStats::default();
+        }
+        let n = state.frames as f64;
+        let mean = state.sum / n;
+        FrameStats {
+            frames: state.frames,
+            mean: Duration::from_secs_f64(mean),
+            max: state.max,
+            jitter: Duration::from_secs_f64((state.sum_sq / n - mean * mean).max(0.).sqrt()),
+        }
+    }
+}
+
+/// Cries once frames stop arriving or are slow for too long.
+struct Lag {
+    state: Arc<Mutex<FrameState>>,
+    stall: Duration,
+    consecutive: usize,
+    cry: Cry,
+}
+
+impl Baby for Lag {
+    fn cry(&mut self, elapsed: usize) -> BoxResult<()> {
+        (self.cry)(elapsed)
+    }
+
+    fn should_cry(&self, _elapsed: usize) -> bool {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.last.elapsed() >= self.stall || state.slow >= self.consecutive.max(1)
+    }
+}
+
+impl Cradle {
+    /// Watches an event loop, e.g. of a GUI or a game, which reports each frame through
+    /// [`Frames::frame`].
+    ///
+    /// The baby calls `cry` with its elapsed time once no frame arrived for `stall`,
+    /// or `consecutive` frames in a row came more than `threshold` after the previous one.
+    pub fn frames<C>(
+        &self,
+        stall: Duration,
+        threshold: Duration,
+        consecutive: usize,
+        cry: C,
+    ) -> Frames
+    where
+        C: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(FrameState {
+            threshold,
+            last: Instant::now(),
+            slow: 0,
+            frames: 0,
+            sum: 0.,
+            sum_sq: 0.,
+            max: Duration::ZERO,
+        }));
+        self.put_baby(Lag {
+            state: state.clone(),
+            stall,
+            consecutive,
+            cry: Box::new(cry),
+        });
+        Frames { state }
+    }
+}
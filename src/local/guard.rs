@@ -0,0 +1,131 @@
+use super::{heartbeat::Cry, Baby, BabyId, BoxResult, Cradle, Heartbeat, Signal};
+use std::{
+    sync::mpsc::{channel, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A watchdog for a single job, removed from its cradle when dropped, see [`Cradle::job`].
+pub struct JobGuard {
+    heartbeat: Heartbeat,
+}
+
+impl JobGuard {
+    /// The baby watching the job.
+    pub fn id(&self) -> BabyId {
+        self.heartbeat.id()
+    }
+
+    /// Tells the cradle the job is still making progress.
+    pub fn beat(&self) {
+        self.heartbeat.beat();
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        let _ = self.heartbeat.tx.send(Signal::Remove(self.id()));
+    }
+}
+
+/// What a [`HeartbeatGuard`] does to its baby when dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnDrop {
+    /// Removes the baby from the cradle, e.g. on leaving a critical section normally.
+    Disarm,
+    /// Makes the baby cry at once, see [`Cradle::cry`].
+    Cry,
+}
+
+/// A guard heartbeating a baby while alive, see [`Cradle::guard`].
+pub struct HeartbeatGuard {
+    heartbeat: Heartbeat,
+    on_drop: OnDrop,
+    pulse: Option<(Sender<()>, thread::JoinHandle<()>)>,
+}
+
+impl HeartbeatGuard {
+    /// Tells the cradle the guarded section is still alive.
+    pub fn beat(&self) {
+        self.heartbeat.beat();
+    }
+}
+
+impl Drop for HeartbeatGuard {
+    fn drop(&mut self) {
+        if let Some((stop, jh)) = self.pulse.take() {
+            drop(stop);
+            let _ = jh.join();
+        }
+        let id = self.heartbeat.id();
+        let signal = match self.on_drop {
+            OnDrop::Disarm => Signal::Remove(id),
+            OnDrop::Cry => Signal::Cry(id),
+        };
+        let _ = self.heartbeat.tx.send(signal);
+    }
+}
+
+/// Cries once the job misses its heartbeat or runs longer than expected.
+struct Job {
+    started: Instant,
+    expected: Duration,
+    heartbeat: usize,
+    cry: Cry,
+}
+
+impl Baby for Job {
+    fn cry(&mut self, elapsed: usize) -> BoxResult<()> {
+        (self.cry)(elapsed)
+    }
+
+    fn should_cry(&self, elapsed: usize) -> bool {
+        elapsed >= self.heartbeat || self.started.elapsed() >= self.expected
+    }
+
+    fn timeout(&self) -> Option<usize> {
+        Some(self.heartbeat)
+    }
+}
+
+impl Cradle {
+    /// Watches a job expected to last `expected` seconds and to [`JobGuard::beat`]
+    /// at least every `heartbeat` seconds, until the returned guard is dropped.
+    ///
+    /// The job calls `cry` with its elapsed time when it misses its heartbeat or overruns.
+    pub fn job<C>(&self, expected: usize, heartbeat: usize, cry: C) -> JobGuard
+    where
+        C: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        let id = self.put_baby(Job {
+            started: Instant::now(),
+            expected: Duration::from_secs(expected as u64),
+            heartbeat,
+            cry: Box::new(cry),
+        });
+        JobGuard {
+            heartbeat: self.heartbeat(id),
+        }
+    }
+
+    /// Guards the baby `id`: it is reset every `every` from a lightweight thread if given,
+    /// or on each [`HeartbeatGuard::beat`], and handled according to `on_drop` once the guard drops.
+    pub fn guard(&self, id: BabyId, every: Option<Duration>, on_drop: OnDrop) -> HeartbeatGuard {
+        let heartbeat = self.heartbeat(id);
+        let pulse = every.map(|every| {
+            let (stop, rx) = channel::<()>();
+            let heartbeat = heartbeat.clone();
+            let jh = thread::spawn(move || {
+                while rx.recv_timeout(every) == Err(RecvTimeoutError::Timeout) {
+                    heartbeat.beat();
+                }
+            });
+            (stop, jh)
+        });
+        HeartbeatGuard {
+            heartbeat,
+            on_drop,
+            pulse,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use super::{Baby, BabyId, BoxResult, Cradle, Signal};
+use std::sync::mpsc::Sender;
+
+/// The cry of a preset baby, called with its elapsed time in seconds like [`Baby::cry`].
+pub(super) type Cry = Box<dyn FnMut(usize) -> BoxResult<()> + Send>;
+
+/// Cries once it has not been reset for `timeout` seconds, like [`Timeout`](super::Timeout)
+/// but with a cry of its own.
+pub(super) struct Stall {
+    pub(super) timeout: usize,
+    pub(super) cry: Cry,
+}
+
+impl Baby for Stall {
+    fn cry(&mut self, elapsed: usize) -> BoxResult<()> {
+        (self.cry)(elapsed)
+    }
+
+    fn timeout(&self) -> Option<usize> {
+        Some(self.timeout)
+    }
+}
+
+/// A handle resetting a single baby, which can be cloned and sent to other threads.
+#[derive(Clone)]
+pub struct Heartbeat {
+    pub(super) tx: Sender<Signal>,
+    id: BabyId,
+}
+
+impl Heartbeat {
+    /// The baby this heartbeat resets.
+    pub fn id(&self) -> BabyId {
+        self.id
+    }
+
+    /// Resets the baby, does nothing once the cradle is gone.
+    pub fn beat(&self) {
+        let _ = self.tx.send(Signal::ResetBaby(self.id));
+    }
+
+    /// Wraps `handler` so that the baby is reset each time it has processed a message.
+    pub fn wrap<M, R, F>(self, mut handler: F) -> impl FnMut(M) -> R + Send
+    where
+        F: FnMut(M) -> R + Send,
+    {
+        move |message| {
+            let processed = handler(message);
+            self.beat();
+            processed
+        }
+    }
+}
+
+impl Cradle {
+    /// A heartbeat resetting the baby `id`.
+    pub fn heartbeat(&self, id: BabyId) -> Heartbeat {
+        Heartbeat {
+            tx: self.tx.clone(),
+            id,
+        }
+    }
+
+    /// Watches a message-queue consumer: puts a baby calling `cry` with its elapsed time
+    /// once no message has been processed for `timeout` seconds, and returns `handler`
+    /// wrapped to reset it.
+    ///
+    /// The wrapper fits the callback of any consumer, Kafka, AMQP or NATS alike.
+    pub fn consumer<M, R, F, C>(
+        &self,
+        timeout: usize,
+        handler: F,
+        cry: C,
+    ) -> impl FnMut(M) -> R + Send
+    where
+        F: FnMut(M) -> R + Send,
+        C: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        let id = self.put_baby(Stall {
+            timeout,
+            cry: Box::new(cry),
+        });
+        self.heartbeat(id).wrap(handler)
+    }
+}
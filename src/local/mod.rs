@@ -1,14 +1,63 @@
 //! Local cradle, running on local machine, does not require network signal.
 
+mod builder;
+#[cfg(feature = "chaos")]
+mod chaos;
+mod child;
+mod core;
+mod event;
+mod fixed;
+mod frames;
+mod guard;
+mod heartbeat;
+mod observer;
+mod panic;
+mod shards;
+mod template;
+mod watchdog;
+mod watched;
+mod worker;
+
+use worker::Slot;
+
+pub use builder::CradleBuilder;
+#[cfg(feature = "chaos")]
+pub use chaos::Chaos;
+pub use child::Child;
+pub use core::{Command, CradleCore};
+pub use event::{BabyState, Event, EventMetadata, Reason};
+pub use fixed::StaticCradle;
+pub use frames::{FrameStats, Frames};
+pub use guard::{HeartbeatGuard, JobGuard, OnDrop};
+pub use heartbeat::Heartbeat;
+pub use observer::CradleObserver;
+pub use template::{BabyTemplate, Labels, Spawned};
+pub use watchdog::Watchdog;
+pub use watched::{WatchedFuture, WatchedMutex, WatchedMutexGuard};
+
 use std::{
-    sync::mpsc::{channel, Sender},
+    any::Any,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc::{channel, Receiver, SendError, Sender},
+        Arc, Mutex, MutexGuard,
+    },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 /// type alias for `Result<T, Box<dyn std::error::Error + Send>>`
 pub type BoxResult<T> = Result<T, Box<dyn std::error::Error + Send>>;
 
+/// Identifier of a baby within its cradle.
+///
+/// Babies given when building the cradle are numbered from 0 in order,
+/// later babies get the ids returned by [`Cradle::put_baby`].
+pub type BabyId = usize;
+
+/// Opaque user data attached to a baby, see [`Baby::metadata`].
+pub type Metadata = Arc<dyn Any + Send + Sync>;
+
 /// A baby that cries after a certain time.
 pub trait Baby {
     /// The cry behavior of the baby.
@@ -16,60 +65,452 @@ pub trait Baby {
     /// # Arguments
     /// elapsed: The elapsed time in seconds.
     fn cry(&mut self, elapsed: usize) -> BoxResult<()>;
+
+    /// Whether the baby should cry, checked before each [`Baby::cry`].
+    ///
+    /// Defaults to crying once [`Baby::timeout`] has elapsed, or every time it is rocked
+    /// without a timeout. Cradle-wide policies, such as [`CradleBuilder::rate_limit`],
+    /// only count cries that pass this check.
+    ///
+    /// # Arguments
+    /// elapsed: The elapsed time in seconds.
+    fn should_cry(&self, elapsed: usize) -> bool {
+        self.timeout().is_none_or(|timeout| elapsed >= timeout)
+    }
+
+    /// The elapsed time in seconds after which the baby starts crying, if known.
+    ///
+    /// It lets the cradle schedule the baby's cries, see [`Cradle::next_deadline`].
+    /// Defaults to `None`.
+    fn timeout(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether the baby has a cry of its own.
+    ///
+    /// Babies without one, such as [`Timeout`], cry through [`CradleBuilder::default_cry`] instead,
+    /// and fail if the cradle has none.
+    /// Defaults to `true`.
+    fn has_cry(&self) -> bool {
+        true
+    }
+
+    /// Cries once for several cries collapsed by [`CradleBuilder::dedup`].
+    ///
+    /// Defaults to a plain [`Baby::cry`].
+    ///
+    /// # Arguments
+    /// elapsed: The elapsed time in seconds.
+    /// repeats: How many cries were collapsed since the last one.
+    fn cry_repeated(&mut self, elapsed: usize, _repeats: usize) -> BoxResult<()> {
+        self.cry(elapsed)
+    }
+
+    /// Babies with the same key share one [`CradleBuilder::dedup`] window.
+    ///
+    /// Defaults to `None`, so that the baby only collapses its own cries.
+    fn dedup_key(&self) -> Option<&str> {
+        None
+    }
+
+    /// The minimum rate of resets, from [`Cradle::reset`] or [`Cradle::reset_baby`],
+    /// below which the baby wants to cry, replacing [`Baby::should_cry`].
+    ///
+    /// Defaults to `None`, see [`Throughput`].
+    fn rate(&self) -> Option<Rate> {
+        None
+    }
+
+    /// Named heartbeat sources of the baby, checking in via [`Cradle::reset_from`].
+    ///
+    /// If any, the baby wants to cry while fewer than [`Baby::quorum`] sources
+    /// checked in within their own interval, replacing [`Baby::should_cry`].
+    /// Defaults to none.
+    fn sources(&self) -> &[Source] {
+        &[]
+    }
+
+    /// How many of the [`Baby::sources`] must have checked in for the baby to be healthy.
+    ///
+    /// Defaults to `None`, requiring all of them.
+    fn quorum(&self) -> Option<usize> {
+        None
+    }
+
+    /// How many resets the baby can bank, to tolerate bursty heartbeats.
+    ///
+    /// Each reset adds a credit up to this cap, and when the baby wants to cry,
+    /// a credit is spent instead, restarting its elapsed time. It only cries once credits run out.
+    /// Defaults to 0, banking nothing.
+    fn bank(&self) -> usize {
+        0
+    }
+
+    /// The time in seconds after which the baby is removed from the cradle with an [`Event::Expired`],
+    /// counted from when it was put in, regardless of resets and cries.
+    ///
+    /// Defaults to `None`, living as long as the cradle.
+    fn ttl(&self) -> Option<usize> {
+        None
+    }
+
+    /// How severe the baby's cries are, see [`CradleBuilder::on_severity`].
+    ///
+    /// Defaults to [`Severity::Warning`].
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// User data carried along with the baby's cries to cradle-level actions,
+    /// e.g. for alert routing, see [`CryContext::metadata`].
+    ///
+    /// [`Event::WouldCry`] and [`Event::StateChanged`] carry it too.
+    ///
+    /// Defaults to `None`.
+    fn metadata(&self) -> Option<Metadata> {
+        None
+    }
+
+    /// The shard the baby is rocked in, see [`CradleBuilder::shards`].
+    ///
+    /// Read once when the baby is put in, along with whether it is [`Severity::Critical`].
+    /// Defaults to `None`, assigning shards by [`BabyId`].
+    fn shard(&self) -> Option<usize> {
+        None
+    }
+
+    /// Called once when the started cradle is gracefully stopped,
+    /// so the baby can tell it is going away on purpose.
+    ///
+    /// Defaults to doing nothing.
+    fn on_shutdown(&mut self) -> BoxResult<()> {
+        Ok(())
+    }
+}
+
+/// What cradle-level actions learn about a cry, such as [`CradleBuilder::on_severity`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct CryContext {
+    /// The crying baby.
+    pub id: BabyId,
+    /// The baby's elapsed time in seconds.
+    pub elapsed: usize,
+    /// How many cries were collapsed into this one by [`CradleBuilder::dedup`].
+    pub repeats: usize,
+    /// The baby's severity.
+    pub severity: Severity,
+    /// The baby's metadata.
+    pub metadata: Option<Metadata>,
+}
+
+/// A baby without a cry of its own, crying through [`CradleBuilder::default_cry`]
+/// once the given number of seconds has elapsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout(pub usize);
+
+impl Baby for Timeout {
+    fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+        Ok(())
+    }
+
+    fn has_cry(&self) -> bool {
+        false
+    }
+
+    fn timeout(&self) -> Option<usize> {
+        Some(self.0)
+    }
+}
+
+/// A summary of the cradle's health, see [`CradleBuilder::health_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Health {
+    /// Babies currently wanting to cry.
+    pub overdue: Vec<BabyId>,
+    /// How many cries happened during the period, batched ones included.
+    pub cries: usize,
+    /// Up to 3 babies which started crying more than once during the period,
+    /// with how many times, the most flapping first.
+    pub flappers: Vec<(BabyId, usize)>,
+    /// Babies whose last cries were dropped by [`CradleBuilder::rate_limit`],
+    /// with how many in a row, the most starved first.
+    pub starved: Vec<(BabyId, usize)>,
+}
+
+/// What changed in the cradle since a cursor, see [`Cradle::status_delta`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Delta {
+    /// The cursor to pass next time.
+    pub cursor: usize,
+    /// The events emitted since the given cursor, oldest first.
+    pub events: Vec<Event>,
+    /// Whether events since the given cursor were dropped, being too old.
+    pub truncated: bool,
+}
+
+/// Where an error handed to [`CradleBuilder::on_error`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorSource {
+    /// The baby's cry, or a cradle-level action for it.
+    Cry(BabyId),
+    /// The digest of [`CradleBuilder::rate_limit`].
+    Digest,
+    /// The handler of [`CradleBuilder::batch`].
+    Batch,
+    /// The handler of [`CradleBuilder::health_report`].
+    HealthReport,
+    /// The baby's [`Baby::on_shutdown`], or [`CradleBuilder::farewell`] for `None`.
+    Shutdown(Option<BabyId>),
+    /// The cradle handle was dropped without stopping the cradle, which keeps rocking.
+    Disconnected,
+}
+
+/// A named heartbeat source, see [`Baby::sources`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Source {
+    /// The name given to [`Cradle::reset_from`].
+    pub name: &'static str,
+    /// The interval in seconds within which the source must check in.
+    pub interval: usize,
+}
+
+/// A minimum rate of resets, see [`Baby::rate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rate {
+    /// The minimum number of resets within the window.
+    pub min: usize,
+    /// The sliding window in seconds.
+    pub window: usize,
+}
+
+/// A baby without a cry of its own, crying through [`CradleBuilder::default_cry`]
+/// while it gets fewer resets than its rate, e.g. "should process at least 100 messages per minute".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throughput(pub Rate);
+
+impl Baby for Throughput {
+    fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+        Ok(())
+    }
+
+    fn has_cry(&self) -> bool {
+        false
+    }
+
+    fn rate(&self) -> Option<Rate> {
+        Some(self.0)
+    }
+}
+
+/// How severe a baby's cries are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Worth noting.
+    Info,
+    /// Worth looking at.
+    Warning,
+    /// Worth waking someone up.
+    ///
+    /// Critical babies cry as soon as their [`Baby::timeout`] elapses instead of on the next tick,
+    /// are rocked first, and always pass [`CradleBuilder::rate_limit`].
+    Critical,
 }
 
 /// A cradle that holds babies.
+///
+/// Once the cradle thread is gone, e.g. stopped by an error, the handle's methods do nothing.
 pub struct Cradle {
     tx: Sender<Signal>,
     jh: thread::JoinHandle<BoxResult<()>>,
+    next_id: AtomicUsize,
+    started: AtomicBool,
+    auto_start: bool,
+    /// Answers the read-only methods, see [`Cradle::observer`].
+    observer: CradleObserver,
+    #[cfg(feature = "chaos")]
+    chaos: Chaos,
 }
 
 impl Cradle {
     /// Instantiates a new cradle.
-    pub fn new<B>(mut babies: Vec<B>) -> Self
+    pub fn new<B>(babies: Vec<B>) -> Self
+    where
+        B: Baby + Send + 'static,
+    {
+        babies
+            .into_iter()
+            .fold(Self::builder(), CradleBuilder::baby)
+            .build()
+    }
+
+    /// Configures a new cradle.
+    pub fn builder() -> CradleBuilder {
+        CradleBuilder::default()
+    }
+
+    /// Puts a baby into the cradle, it can be called before or after the cradle starts.
+    ///
+    /// The baby's elapsed time starts from 0 when it is put in.
+    pub fn put_baby<B>(&self, baby: B) -> BabyId
     where
-        B: Baby + Send + Sync + 'static,
+        B: Baby + Send + 'static,
     {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let _ = self.tx.send(Signal::Put(id, Box::new(baby)));
+        if self.auto_start {
+            self.start();
+        }
+        id
+    }
+
+    /// Starts the cradle, returns `false` if it has already been started.
+    pub fn start(&self) -> bool {
+        let first = !self.started.swap(true, Ordering::Relaxed);
+        if first {
+            let _ = self.tx.send(Signal::Start);
+        }
+        first
+    }
+
+    /// Resets the cradle's elapsed time, so that babies will not cry.
+    ///
+    /// It neither allocates nor locks, so it can be called at high frequency from latency-critical code.
+    /// The cradle catches up on its next wakeup, [`Cradle::next_deadline`] lagging until then.
+    pub fn reset(&self) {
+        #[cfg(feature = "chaos")]
+        if self.chaos.drops_reset() {
+            return;
+        }
+        self.observer.resets.reset();
+    }
+
+    /// The elapsed time since the cradle started or was last reset, zero before it starts.
+    pub fn elapsed(&self) -> Duration {
+        self.observer.elapsed()
+    }
+
+    /// When the next baby is due to cry, according to [`Baby::timeout`].
+    ///
+    /// `None` if the cradle has not started, is muted, or no baby has a timeout,
+    /// timeouts too far to fit in an [`Instant`], such as `usize::MAX`, counting as none.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.observer.next_deadline()
+    }
+
+    /// When each baby is due to cry within `horizon` from now, assuming no further resets,
+    /// earliest first. Overdue babies are due now.
+    ///
+    /// Only babies with a [`Baby::timeout`] can be predicted,
+    /// nothing is due while the cradle is muted or not yet started.
+    /// A `horizon` too far to fit in an [`Instant`], such as [`Duration::MAX`], is unbounded.
+    pub fn predict(&self, horizon: Duration) -> Vec<(BabyId, Instant)> {
+        self.observer.predict(horizon)
+    }
+
+    /// Resets a single baby's elapsed time, unknown ids are ignored.
+    pub fn reset_baby(&self, id: BabyId) {
+        let _ = self.tx.send(Signal::ResetBaby(id));
+    }
+
+    /// Checks a baby's heartbeat `source` in, see [`Baby::sources`].
+    pub fn reset_from(&self, id: BabyId, source: &str) {
+        let _ = self.tx.send(Signal::ResetFrom(id, source.to_owned()));
+    }
+
+    /// When each of the baby's [`Baby::sources`] last checked in, `None` for unknown babies.
+    pub fn last_seen(&self, id: BabyId) -> Option<Vec<(&'static str, Option<Instant>)>> {
+        self.observer.last_seen(id)
+    }
+
+    /// The total time spent in the baby's cries so far, cradle-level actions included,
+    /// `None` for unknown babies.
+    pub fn cry_time(&self, id: BabyId) -> Option<Duration> {
+        self.observer.cry_time(id)
+    }
+
+    /// Mutes the cradle: babies are still rocked and keep their elapsed time,
+    /// but none of them cries until [`Cradle::unmute`], each cry becoming an [`Event::WouldCry`]
+    /// instead, and no action runs.
+    pub fn mute(&self) {
+        let _ = self.tx.send(Signal::Mute);
+    }
+
+    /// Unmutes the cradle, babies cry again as usual.
+    pub fn unmute(&self) {
+        let _ = self.tx.send(Signal::Unmute);
+    }
+
+    /// Mutes a single baby for `duration`, after which it is unmuted with an [`Event::Unmuted`].
+    /// Meanwhile, its cries become [`Event::WouldCry`] as with [`Cradle::mute`].
+    ///
+    /// Muting it again replaces the previous duration, unknown ids are ignored.
+    /// [`Duration::MAX`] mutes it for good.
+    pub fn mute_for(&self, id: BabyId, duration: Duration) {
+        let until = later(Instant::now(), duration);
+        let _ = self.tx.send(Signal::MuteFor(id, until));
+    }
+
+    /// Moves a baby into `other`, keeping its elapsed time, mute and overdue state,
+    /// and returns its id there, or `None` if this cradle has no such baby.
+    ///
+    /// If `other`'s thread is gone, the baby stays in this cradle and `None` is returned.
+    pub fn transfer(&self, id: BabyId, other: &Cradle) -> Option<BabyId> {
+        if other.jh.is_finished() {
+            return None;
+        }
         let (tx, rx) = channel();
-        let jh = thread::spawn(move || {
-            if Signal::Start == rx.recv().unwrap() {
-                let mut elapsed = 0;
-                loop {
-                    let signal = rx.try_recv();
-                    match signal {
-                        Ok(signal) => match signal {
-                            Signal::Reset => elapsed = 0,
-                            Signal::Stop => break,
-                            _ => {}
-                        },
-                        _ => {
-                            for baby in babies.iter_mut() {
-                                baby.cry(elapsed)?;
-                            }
-                            thread::sleep(Duration::from_secs(1));
-                            elapsed += 1;
-                        }
-                    }
+        self.tx.send(Signal::Take(id, tx)).ok()?;
+        let mut slot = rx.recv().ok()?;
+        slot.id = other.next_id.fetch_add(1, Ordering::Relaxed);
+        let new_id = slot.id;
+        match other.tx.send(Signal::Insert(slot)) {
+            Ok(()) => Some(new_id),
+            Err(SendError(signal)) => {
+                // `other` exited meanwhile, put the baby back.
+                if let Signal::Insert(mut slot) = signal {
+                    slot.id = id;
+                    let _ = self.tx.send(Signal::Insert(slot));
                 }
+                None
             }
-            Ok(())
-        });
-        Self { tx, jh }
+        }
     }
 
-    /// Starts the cradle.
-    pub fn start(&self) {
-        self.tx.send(Signal::Start).unwrap();
+    /// Makes a baby cry right away, whatever its elapsed time and [`Baby::should_cry`].
+    ///
+    /// Mutes still apply, making it an [`Event::WouldCry`], unknown ids are ignored.
+    pub fn cry(&self, id: BabyId) {
+        let _ = self.tx.send(Signal::Cry(id));
     }
 
-    /// Resets the cradle's elapsed time, so that babies will not cry.
-    pub fn reset(&self) {
-        self.tx.send(Signal::Reset).unwrap();
+    /// Removes a baby from the cradle, unknown ids are ignored.
+    pub fn remove(&self, id: BabyId) {
+        let _ = self.tx.send(Signal::Remove(id));
+    }
+
+    /// Subscribes to the cradle's events, the receiver gets every event from now on.
+    pub fn subscribe(&self) -> Receiver<Event> {
+        self.observer.subscribe()
+    }
+
+    /// What changed since `cursor`, for dashboards polling many cradles:
+    /// the events emitted since then, and the cursor to pass next time.
+    ///
+    /// Pass 0 the first time. Only the latest 1024 events are kept,
+    /// [`Delta::truncated`] tells when older ones were missed.
+    pub fn status_delta(&self, cursor: usize) -> Delta {
+        self.observer.status_delta(cursor)
     }
 
     /// Gracefully stops the cradle.
+    ///
+    /// A started cradle says goodbye first, see [`Baby::on_shutdown`] and [`CradleBuilder::farewell`].
     pub fn stop(&self) {
-        self.tx.send(Signal::Stop).unwrap();
+        let _ = self.tx.send(Signal::Stop);
     }
 
     /// Joins the cradle thread.
@@ -78,17 +519,182 @@ impl Cradle {
     }
 }
 
-#[derive(PartialEq)]
+/// What the cradle thread tells the handle.
+#[derive(Default)]
+struct Status {
+    reset_at: Option<Instant>,
+    next_deadline: Option<Instant>,
+    /// The last time babies were rocked.
+    last_tick: Option<Instant>,
+    /// The time between two ticks.
+    period: Duration,
+    /// Whether the cradle has been gracefully stopped.
+    stopped: bool,
+    /// Whether the lock was recovered from a panic, see [`Event::RegistryPoisoned`].
+    poisoned: bool,
+}
+
+/// Resets of the whole cradle, counted by the handle and caught up with by the worker.
+struct Resets {
+    base: Instant,
+    /// How many resets happened.
+    seq: AtomicUsize,
+    /// When the last one happened, in nanoseconds since `base`.
+    last: AtomicU64,
+}
+
+impl Resets {
+    fn new() -> Self {
+        Self {
+            base: Instant::now(),
+            seq: AtomicUsize::new(0),
+            last: AtomicU64::new(0),
+        }
+    }
+
+    fn reset(&self) {
+        let nanos = self.base.elapsed().as_nanos() as u64;
+        // Concurrent resets may land out of order, the latest one wins.
+        self.last.fetch_max(nanos, Ordering::AcqRel);
+        self.seq.fetch_add(1, Ordering::Release);
+    }
+
+    /// When the last reset happened, if any.
+    fn last(&self) -> Option<Instant> {
+        (self.seq.load(Ordering::Acquire) > 0)
+            .then(|| self.base + Duration::from_nanos(self.last.load(Ordering::Acquire)))
+    }
+}
+
+/// How far [`later`] saturates, about a century.
+const FOREVER: Duration = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+
+/// `duration` after `at`, saturating at [`FOREVER`] for durations too long to fit in an [`Instant`],
+/// such as [`Duration::MAX`].
+fn later(at: Instant, duration: Duration) -> Instant {
+    at.checked_add(duration)
+        .or_else(|| at.checked_add(FOREVER))
+        .unwrap_or(at)
+}
+
+impl Status {
+    /// Locks the status, which stays consistent even if a thread panicked while holding it,
+    /// recovering the lock so that the cradle thread reports it once.
+    fn lock(status: &Mutex<Status>) -> MutexGuard<'_, Status> {
+        status.lock().unwrap_or_else(|e| {
+            status.clear_poison();
+            let mut status = e.into_inner();
+            status.poisoned = true;
+            status
+        })
+    }
+}
+
 enum Signal {
-    Reset,
+    ResetBaby(BabyId),
+    ResetFrom(BabyId, String),
+    LastSeen(BabyId, Sender<Vec<(&'static str, Option<Instant>)>>),
     Start,
     Stop,
+    Put(BabyId, Box<dyn Baby + Send>),
+    Mute,
+    Unmute,
+    MuteFor(BabyId, Instant),
+    Subscribe(Sender<Event>),
+    Take(BabyId, Sender<Slot>),
+    Remove(BabyId),
+    Cry(BabyId),
+    Panic(BabyId, String, Sender<()>),
+    Predict(Duration, Sender<Vec<(BabyId, Instant)>>),
+    Delta(usize, Sender<Delta>),
+    CryTime(BabyId, Sender<Duration>),
+    Insert(Slot),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Counts its cries.
+    struct Counter(Arc<AtomicUsize>);
+
+    impl Baby for Counter {
+        fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    /// Counts its cries, due after `.0` seconds.
+    struct Patient(usize, Arc<AtomicUsize>);
+
+    impl Baby for Patient {
+        fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+            self.1.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+
+        fn timeout(&self) -> Option<usize> {
+            Some(self.0)
+        }
+    }
+
+    /// Counts its collapsed cries, see [`CradleBuilder::dedup`].
+    struct Repeated(Arc<AtomicUsize>);
+
+    impl Baby for Repeated {
+        fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+            Ok(())
+        }
+
+        fn cry_repeated(&mut self, _elapsed: usize, repeats: usize) -> BoxResult<()> {
+            self.0.fetch_add(repeats, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    /// Fails each time it cries.
+    struct Failing;
+
+    impl Baby for Failing {
+        fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+            Err(Box::new(std::fmt::Error))
+        }
+    }
+
+    /// Expires after `.0` seconds.
+    struct Ephemeral(usize);
+
+    impl Baby for Ephemeral {
+        fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+            Ok(())
+        }
+
+        fn ttl(&self) -> Option<usize> {
+            Some(self.0)
+        }
+    }
+
+    /// A cry counting its calls in `count`.
+    fn counting(count: &Arc<AtomicUsize>) -> impl FnMut(usize) -> BoxResult<()> + Send + 'static {
+        let count = count.clone();
+        move |_elapsed| {
+            count.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    /// A cradle-level action counting its calls in `count`, e.g. a default cry.
+    fn counting_action(
+        count: &Arc<AtomicUsize>,
+    ) -> impl FnMut(&CryContext) -> BoxResult<()> + Send + 'static {
+        let count = count.clone();
+        move |_cry| {
+            count.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
     #[test]
     fn test_cradle() {
         struct BabyImpl {
@@ -111,4 +717,1393 @@ mod tests {
         cradle.stop();
         cradle.join().unwrap().unwrap();
     }
+
+    #[test]
+    fn test_put_baby() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::new(Vec::<Counter>::new());
+        assert_eq!(cradle.put_baby(Counter(count.clone())), 0);
+        cradle.start();
+        assert_eq!(cradle.put_baby(Counter(count.clone())), 1);
+        thread::sleep(Duration::from_millis(1500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Both babies are rocked, the first one from the very beginning.
+        assert!(count.load(Ordering::Relaxed) >= 3);
+    }
+
+    #[test]
+    fn test_align() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Counter(count.clone()))
+            .align(Duration::from_secs(2))
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(4500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Rocked at start, then only on the 2 or 3 even seconds passed.
+        let count = count.load(Ordering::Relaxed);
+        assert!((2..=4).contains(&count));
+    }
+
+    #[test]
+    fn test_mute() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::new(vec![Counter(count.clone())]);
+        cradle.mute();
+        cradle.start();
+        thread::sleep(Duration::from_millis(1500));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        cradle.unmute();
+        thread::sleep(Duration::from_millis(1000));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert!(count.load(Ordering::Relaxed) >= 1);
+    }
+
+    #[test]
+    fn test_mute_records() {
+        let cradle = Cradle::builder().baby(Timeout(0)).state_events().build();
+        let events = cradle.subscribe();
+        cradle.mute();
+        cradle.start();
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap();
+        let late = Event::StateChanged {
+            id: 0,
+            from: BabyState::Sleeping,
+            to: BabyState::Late,
+            reason: Reason::TimeoutElapsed,
+            metadata: None,
+        };
+        assert_eq!(event, late);
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(
+            event,
+            Event::WouldCry {
+                id: 0,
+                elapsed: 0,
+                metadata: None,
+            }
+        );
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_mute_for() {
+        let muted = Arc::new(AtomicUsize::new(0));
+        let other = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::new(vec![Counter(muted.clone()), Counter(other.clone())]);
+        let events = cradle.subscribe();
+        cradle.mute_for(0, Duration::from_millis(500));
+        cradle.start();
+        // Cries held back by the mute are recorded first.
+        let event = events.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert_eq!(
+            event,
+            Event::WouldCry {
+                id: 0,
+                elapsed: 0,
+                metadata: None,
+            }
+        );
+        let event = events.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert_eq!(event, Event::Unmuted(0));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert!(muted.load(Ordering::Relaxed) < other.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_forever() {
+        let cradle = Cradle::builder()
+            .baby(Counter(Arc::default()))
+            .baby(Counter(Arc::default()))
+            .grace(Duration::MAX)
+            .cry_budget(Duration::ZERO, 1, Duration::MAX)
+            .build();
+        let events = cradle.subscribe();
+        cradle.mute_for(0, Duration::MAX);
+        cradle.start();
+        cradle.cry(0);
+        cradle.cry(1);
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(
+            event,
+            Event::WouldCry {
+                id: 0,
+                elapsed: 0,
+                metadata: None,
+            }
+        );
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(event, Event::OverBudget(1));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Counter(count.clone()))
+            .rate_limit(2, |_dropped| Ok(()))
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(2500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let repeats = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Counter(count.clone()))
+            .baby(Repeated(repeats.clone()))
+            .dedup(Duration::from_secs(2))
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(2500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Cries at 0 and 2, the one at 1 is collapsed into the latter.
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        assert_eq!(repeats.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_dedup_expires() {
+        let repeats = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Repeated(repeats.clone()))
+            .dedup(Duration::from_secs(2))
+            .build();
+        cradle.start();
+        // Cries at 0 and 1, the latter collapsed, then muted for a while.
+        thread::sleep(Duration::from_millis(1500));
+        cradle.mute();
+        thread::sleep(Duration::from_millis(2000));
+        cradle.unmute();
+        thread::sleep(Duration::from_millis(1000));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(repeats.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_consecutive() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Counter(count.clone()))
+            .consecutive(2)
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(2500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Rocked at 0, 1 and 2, but the first tick alone is not enough to cry.
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_on_severity() {
+        struct Critical;
+        impl Baby for Critical {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                Ok(())
+            }
+            fn severity(&self) -> Severity {
+                Severity::Critical
+            }
+        }
+        let paged = Arc::new(AtomicUsize::new(0));
+        let logged = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Critical)
+            .on_severity(Severity::Critical, counting_action(&paged))
+            .on_severity(Severity::Warning, counting_action(&logged))
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(paged.load(Ordering::Relaxed), 1);
+        assert_eq!(logged.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_metadata() {
+        struct Tagged;
+        impl Baby for Tagged {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                Ok(())
+            }
+            fn metadata(&self) -> Option<Metadata> {
+                Some(Arc::new("database"))
+            }
+        }
+        let routed = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Tagged)
+            .on_severity(Severity::Warning, {
+                let routed = routed.clone();
+                move |cry| {
+                    let team = cry.metadata.as_ref().unwrap().downcast_ref::<&str>();
+                    assert_eq!(team, Some(&"database"));
+                    routed.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }
+            })
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(routed.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_event_metadata() {
+        struct Tagged(Metadata);
+        impl Baby for Tagged {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                Ok(())
+            }
+            fn metadata(&self) -> Option<Metadata> {
+                Some(self.0.clone())
+            }
+        }
+        let metadata: Metadata = Arc::new("database");
+        let cradle = Cradle::builder()
+            .baby(Tagged(metadata.clone()))
+            .silent()
+            .build();
+        let events = cradle.subscribe();
+        cradle.start();
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap();
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        let Event::WouldCry {
+            metadata: Some(carried),
+            ..
+        } = &event
+        else {
+            panic!("unexpected event {event:?}");
+        };
+        assert_eq!(carried.downcast_ref::<&str>(), Some(&"database"));
+        let expected = Event::WouldCry {
+            id: 0,
+            elapsed: 0,
+            metadata: Some(EventMetadata(metadata)),
+        };
+        assert_eq!(event, expected);
+    }
+
+    #[test]
+    fn test_farewell() {
+        struct Leaving(Arc<AtomicUsize>);
+        impl Baby for Leaving {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                Ok(())
+            }
+            fn on_shutdown(&mut self) -> BoxResult<()> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+        }
+        let goodbyes = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Leaving(goodbyes.clone()))
+            .farewell({
+                let goodbyes = goodbyes.clone();
+                move || {
+                    goodbyes.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }
+            })
+            .build();
+        cradle.start();
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(goodbyes.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_auto_start() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder().auto_start().build();
+        cradle.put_baby(Counter(count.clone()));
+        assert!(!cradle.start());
+        thread::sleep(Duration::from_millis(500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_next_deadline() {
+        let cradle = Cradle::new(vec![Patient(2, Arc::default())]);
+        assert_eq!(cradle.next_deadline(), None);
+        cradle.start();
+        thread::sleep(Duration::from_millis(100));
+        let deadline = cradle.next_deadline().unwrap();
+        let due = deadline - Instant::now();
+        assert!(due > Duration::from_secs(1) && due <= Duration::from_secs(2));
+        thread::sleep(Duration::from_millis(500));
+        cradle.reset();
+        thread::sleep(Duration::from_millis(100));
+        assert!(cradle.elapsed() < Duration::from_millis(200));
+        // The deadline moves once the cradle wakes up.
+        thread::sleep(Duration::from_millis(1000));
+        assert!(cradle.next_deadline().unwrap() > deadline);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_never_due() {
+        let cradle = Cradle::builder()
+            .baby(Timeout(usize::MAX))
+            .default_cry(|_cry| Ok(()))
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(cradle.next_deadline(), None);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_ticker() {
+        let cradle = Cradle::builder()
+            .baby(Patient(3, Arc::default()))
+            .ticker(Duration::from_secs(1), [0])
+            .build();
+        let events = cradle.subscribe();
+        cradle.start();
+        let Event::Countdown { id, remaining } = events.recv().unwrap() else {
+            panic!("expected a countdown");
+        };
+        assert_eq!(id, 0);
+        assert!(remaining > Duration::from_secs(2) && remaining <= Duration::from_secs(3));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_default_cry() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Timeout(0))
+            .baby(Timeout(10))
+            .default_cry({
+                let count = count.clone();
+                move |cry| {
+                    assert_eq!(cry.id, 0);
+                    count.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }
+            })
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_unheard() {
+        let (tx, rx) = channel();
+        let cradle = Cradle::builder()
+            .baby(Timeout(0))
+            .on_error(move |err, source| {
+                tx.send((err.to_string(), source)).unwrap();
+            })
+            .build();
+        cradle.start();
+        let (message, source) = rx.recv_timeout(Duration::from_secs(1)).unwrap();
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(source, ErrorSource::Cry(0));
+        assert!(message.contains("without a cry"));
+    }
+
+    #[test]
+    fn test_on_error() {
+        let errors = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Failing)
+            .on_error({
+                let errors = errors.clone();
+                move |_err, source| {
+                    assert_eq!(source, ErrorSource::Cry(0));
+                    errors.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(1500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(errors.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_adopt() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let parent = Cradle::builder().build();
+        let child = Cradle::new(vec![Failing]);
+        let id = parent.adopt(&child, counting(&count));
+        assert_eq!(id, 0);
+        parent.start();
+        child.start();
+        thread::sleep(Duration::from_millis(3500));
+        parent.stop();
+        parent.join().unwrap().unwrap();
+        // The child died on its first tick, and is missed after two more.
+        assert!(count.load(Ordering::Relaxed) >= 1);
+        assert!(child.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_transfer() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let a = Cradle::new(vec![Counter(count.clone())]);
+        let b = Cradle::new(vec![Counter(Arc::default())]);
+        a.start();
+        b.start();
+        assert_eq!(a.transfer(0, &b), Some(1));
+        assert_eq!(a.transfer(0, &b), None);
+        a.stop();
+        a.join().unwrap().unwrap();
+        let before = count.load(Ordering::Relaxed);
+        thread::sleep(Duration::from_millis(1500));
+        b.stop();
+        b.join().unwrap().unwrap();
+        // Still rocked, by `b` now.
+        assert!(count.load(Ordering::Relaxed) > before);
+    }
+
+    #[test]
+    fn test_transfer_to_stopped() {
+        let a = Cradle::new(vec![Timeout(60)]);
+        let b = Cradle::new(Vec::<Timeout>::new());
+        a.start();
+        b.stop();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(a.transfer(0, &b), None);
+        // Still in `a`.
+        assert_eq!(a.predict(Duration::from_secs(60)).len(), 1);
+        a.stop();
+        a.join().unwrap().unwrap();
+        b.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_critical() {
+        struct Urgent(Arc<AtomicUsize>, Severity);
+        impl Baby for Urgent {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            fn timeout(&self) -> Option<usize> {
+                Some(1)
+            }
+            fn severity(&self) -> Severity {
+                self.1
+            }
+        }
+        let critical = Arc::new(AtomicUsize::new(0));
+        let warning = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Urgent(warning.clone(), Severity::Warning))
+            .baby(Urgent(critical.clone(), Severity::Critical))
+            .align(Duration::from_secs(3600))
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(1500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Only the critical baby cries before the next hourly tick.
+        assert_eq!(critical.load(Ordering::Relaxed), 1);
+        assert_eq!(warning.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_batch() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let batched = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Counter(count.clone()))
+            .baby(Counter(count.clone()))
+            .baby(Timeout(10))
+            .batch(2, {
+                let batched = batched.clone();
+                move |cries| {
+                    assert_eq!(cries.iter().map(|cry| cry.id).collect::<Vec<_>>(), [0, 1]);
+                    batched.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }
+            })
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        assert_eq!(batched.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_batch_empty() {
+        let batched = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Timeout(10))
+            .baby(Counter(Arc::default()))
+            .silent()
+            .batch(0, {
+                let batched = batched.clone();
+                move |_cries| {
+                    batched.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }
+            })
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(1500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(batched.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_health_report() {
+        let (tx, rx) = channel();
+        let cradle = Cradle::builder()
+            .baby(Counter(Arc::default()))
+            .baby(Timeout(10))
+            .health_report(Duration::from_secs(1), move |health| {
+                tx.send(health.clone()).unwrap();
+                Ok(())
+            })
+            .build();
+        cradle.start();
+        let health = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(health.overdue, [0]);
+        assert_eq!(health.cries, 2);
+        assert!(health.flappers.is_empty());
+        assert!(health.starved.is_empty());
+    }
+
+    #[test]
+    fn test_starved() {
+        let (tx, rx) = channel();
+        let cradle = Cradle::builder()
+            .baby(Counter(Arc::default()))
+            .baby(Counter(Arc::default()))
+            .rate_limit(1, |_dropped| Ok(()))
+            .health_report(Duration::from_secs(1), move |health| {
+                tx.send(health.clone()).unwrap();
+                Ok(())
+            })
+            .build();
+        cradle.start();
+        let health = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(health.starved, [(1, 2), (0, 1)]);
+    }
+
+    #[test]
+    fn test_silent() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Counter(count.clone()))
+            .silent()
+            .build();
+        let events = cradle.subscribe();
+        cradle.start();
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap();
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(
+            event,
+            Event::WouldCry {
+                id: 0,
+                elapsed: 0,
+                metadata: None,
+            }
+        );
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_predict() {
+        let cradle = Cradle::new(vec![Timeout(5), Timeout(1), Timeout(100)]);
+        assert!(cradle.predict(Duration::from_secs(10)).is_empty());
+        cradle.start();
+        let cries = cradle.predict(Duration::from_secs(10));
+        // Taken after the prediction, so that it is not before the cradle started.
+        let now = Instant::now();
+        // An unbounded horizon does not take the cradle down.
+        assert_eq!(cradle.predict(Duration::MAX).len(), 3);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        let ids: Vec<_> = cries.iter().map(|&(id, _)| id).collect();
+        assert_eq!(ids, [1, 0]);
+        assert!(cries[1].1 - now <= Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_ttl() {
+        let cradle = Cradle::new(vec![Ephemeral(1)]);
+        let events = cradle.subscribe();
+        cradle.start();
+        let event = events.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert_eq!(event, Event::Expired(0));
+        let other = Cradle::new(Vec::<Ephemeral>::new());
+        assert_eq!(cradle.transfer(0, &other), None);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_throughput() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let rate = Rate { min: 1, window: 1 };
+        let cradle = Cradle::builder()
+            .baby(Throughput(rate))
+            .default_cry(counting_action(&count))
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(500));
+        cradle.reset_baby(0);
+        thread::sleep(Duration::from_millis(1800));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Enough resets at 1, but the one at 0.5 is out of the window at 2.
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_bank() {
+        struct Bursty(Arc<AtomicUsize>);
+        impl Baby for Bursty {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            fn timeout(&self) -> Option<usize> {
+                Some(1)
+            }
+            fn bank(&self) -> usize {
+                2
+            }
+        }
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::new(vec![Bursty(count.clone())]);
+        cradle.start();
+        // A burst of 3 resets, only 2 of them banked.
+        (0..3).for_each(|_| cradle.reset_baby(0));
+        thread::sleep(Duration::from_millis(2500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Both credits are spent at 1 and 2, so the baby has not cried yet.
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_sources() {
+        struct Replicated(Arc<AtomicUsize>, Option<usize>);
+        impl Baby for Replicated {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            fn sources(&self) -> &[Source] {
+                &[
+                    Source {
+                        name: "primary",
+                        interval: 2,
+                    },
+                    Source {
+                        name: "replica",
+                        interval: 2,
+                    },
+                ]
+            }
+            fn quorum(&self) -> Option<usize> {
+                self.1
+            }
+        }
+        let all = Arc::new(AtomicUsize::new(0));
+        let any = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::new(vec![
+            Replicated(all.clone(), None),
+            Replicated(any.clone(), Some(1)),
+        ]);
+        cradle.start();
+        thread::sleep(Duration::from_millis(500));
+        cradle.reset_from(0, "primary");
+        cradle.reset_from(1, "primary");
+        thread::sleep(Duration::from_millis(1800));
+        let last_seen = cradle.last_seen(0).unwrap();
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // The replica never checked in, which only matters to the first baby.
+        assert_eq!(all.load(Ordering::Relaxed), 1);
+        assert_eq!(any.load(Ordering::Relaxed), 0);
+        assert!(last_seen[0].1.is_some() && last_seen[1].1.is_none());
+    }
+
+    #[test]
+    fn test_consumer() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder().build();
+        let mut consume = cradle.consumer(1, |message: usize| message * 2, counting(&count));
+        cradle.start();
+        for message in 0..4 {
+            assert_eq!(consume(message), message * 2);
+            thread::sleep(Duration::from_millis(300));
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        // Consumption stalls.
+        thread::sleep(Duration::from_millis(1300));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert!(count.load(Ordering::Relaxed) >= 1);
+    }
+
+    #[test]
+    fn test_job() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder().build();
+        cradle.start();
+        let guard = cradle.job(1, 10, counting(&count));
+        thread::sleep(Duration::from_millis(1300));
+        guard.beat();
+        // Overrunning, even though it beats.
+        assert!(count.load(Ordering::Relaxed) >= 1);
+        assert_eq!(cradle.predict(Duration::from_secs(60)).len(), 1);
+        drop(guard);
+        assert!(cradle.predict(Duration::from_secs(60)).is_empty());
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_guard() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Timeout(1))
+            .default_cry(counting_action(&count))
+            .build();
+        cradle.start();
+        let guard = cradle.guard(0, Some(Duration::from_millis(300)), OnDrop::Cry);
+        thread::sleep(Duration::from_millis(1500));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        drop(guard);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_watched_mutex() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder().build();
+        let mutex = WatchedMutex::new(&cradle, 0, 1, counting(&count));
+        cradle.start();
+        thread::sleep(Duration::from_millis(1500));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        {
+            let mut value = mutex.lock().unwrap();
+            *value += 1;
+            thread::sleep(Duration::from_millis(2500));
+        }
+        let cries = count.load(Ordering::Relaxed);
+        assert!(cries > 0);
+        thread::sleep(Duration::from_millis(1200));
+        assert_eq!(count.load(Ordering::Relaxed), cries);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_watched_mutex_idle() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder().build();
+        let mutex = WatchedMutex::new(&cradle, (), 1, counting(&count));
+        cradle.start();
+        // Long idle, then short holds: none must count the idle time.
+        thread::sleep(Duration::from_millis(2500));
+        let deadline = cradle.next_deadline();
+        for _ in 0..20 {
+            let _guard = mutex.lock().unwrap();
+            thread::sleep(Duration::from_millis(100));
+        }
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        assert_eq!(deadline, None);
+    }
+
+    #[test]
+    fn test_watch() {
+        use std::{
+            future::{self, Future},
+            pin::pin,
+            task::{Context, Poll, Waker},
+        };
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder().build();
+        let mut future = pin!(cradle.watch(future::pending::<()>(), 1, counting(&count)));
+        let mut cx = Context::from_waker(Waker::noop());
+        cradle.start();
+        for _ in 0..4 {
+            assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+            thread::sleep(Duration::from_millis(300));
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        thread::sleep(Duration::from_millis(2500));
+        assert!(count.load(Ordering::Relaxed) > 0);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_frames() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder().build();
+        let frames = cradle.frames(
+            Duration::from_secs(1),
+            Duration::from_millis(50),
+            3,
+            counting(&count),
+        );
+        cradle.start();
+        for _ in 0..60 {
+            frames.frame();
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        for _ in 0..3 {
+            thread::sleep(Duration::from_millis(100));
+            frames.frame();
+        }
+        let stats = frames.stats();
+        assert_eq!(stats.frames, 63);
+        assert!(stats.max >= Duration::from_millis(100));
+        assert!(stats.jitter > Duration::ZERO);
+        // Less than the stall, only slow frames make it cry.
+        thread::sleep(Duration::from_millis(800));
+        assert!(count.load(Ordering::Relaxed) > 0);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_cry_on_panic() {
+        let message = Arc::new(Mutex::new(None));
+        let cradle = Cradle::builder()
+            .baby(Timeout(60))
+            .default_cry({
+                let message = message.clone();
+                move |cry| {
+                    *message.lock().unwrap() = cry
+                        .metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.downcast_ref::<String>())
+                        .cloned();
+                    Ok(())
+                }
+            })
+            .build();
+        let _serial = serial_panics();
+        let previous = std::panic::take_hook();
+        cradle.cry_on_panic(0);
+        assert!(thread::spawn(|| panic!("boom")).join().is_err());
+        std::panic::set_hook(previous);
+        assert!(message.lock().unwrap().as_ref().unwrap().contains("boom"));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_cry_on_panic_in_cradle() {
+        struct Panicking;
+        impl Baby for Panicking {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                panic!("boom");
+            }
+        }
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Timeout(60))
+            .baby(Panicking)
+            .default_cry(counting_action(&count))
+            .on_error(|_err, _source| {})
+            .build();
+        let _serial = serial_panics();
+        let previous = std::panic::take_hook();
+        cradle.cry_on_panic(0);
+        let start = Instant::now();
+        cradle.cry(1);
+        // The cradle answers right away, not waiting on itself.
+        cradle.predict(Duration::ZERO);
+        std::panic::set_hook(previous);
+        assert!(start.elapsed() < Duration::from_millis(500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    /// Serializes tests panicking on purpose or hooking panics, which are process-wide.
+    fn serial_panics() -> MutexGuard<'static, ()> {
+        static PANICS: Mutex<()> = Mutex::new(());
+        PANICS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    #[test]
+    fn test_panicked() {
+        let _serial = serial_panics();
+        struct Panicking;
+        impl Baby for Panicking {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                panic!("boom");
+            }
+        }
+        let errors = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Panicking)
+            .on_error({
+                let errors = errors.clone();
+                move |err, source| {
+                    assert_eq!(source, ErrorSource::Cry(0));
+                    assert!(err.to_string().contains("boom"));
+                    errors.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+            .build();
+        let events = cradle.subscribe();
+        cradle.start();
+        assert_eq!(
+            events.recv_timeout(Duration::from_millis(500)),
+            Ok(Event::Panicked(ErrorSource::Cry(0)))
+        );
+        // The cradle is still alive.
+        cradle.put_baby(Timeout(60));
+        cradle.mute();
+        assert!(cradle.next_deadline().is_none());
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(errors.load(Ordering::Relaxed), 1);
+        // Without a handler, the cradle stops cleanly and its handle does not panic.
+        let cradle = Cradle::new(vec![Panicking]);
+        cradle.start();
+        thread::sleep(Duration::from_millis(100));
+        cradle.put_baby(Timeout(60));
+        cradle.mute();
+        assert!(cradle.predict(Duration::from_secs(60)).is_empty());
+        cradle.stop();
+        assert!(cradle.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_registry_poisoned() {
+        let _serial = serial_panics();
+        let cradle = Cradle::new(vec![Timeout(60)]);
+        let events = cradle.subscribe();
+        cradle.start();
+        let status = cradle.observer.status.clone();
+        let poisoner = thread::spawn(move || {
+            let _status = status.lock().unwrap();
+            panic!("boom");
+        });
+        assert!(poisoner.join().is_err());
+        // Neither the handle nor the cradle panic, the cradle telling once.
+        assert!(cradle.next_deadline().is_some());
+        cradle.reset_baby(0);
+        cradle.reset_baby(0);
+        assert_eq!(
+            events.recv_timeout(Duration::from_millis(500)),
+            Ok(Event::RegistryPoisoned)
+        );
+        assert!(events.recv_timeout(Duration::from_millis(200)).is_err());
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_shards() {
+        struct Odd(Arc<AtomicUsize>);
+        impl Baby for Odd {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            fn shard(&self) -> Option<usize> {
+                Some(1)
+            }
+        }
+        let even = Arc::new(AtomicUsize::new(0));
+        let odd = Arc::new(AtomicUsize::new(0));
+        let pinned = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Counter(even.clone()))
+            .baby(Counter(odd.clone()))
+            .baby(Odd(pinned.clone()))
+            .shards(2)
+            .build();
+        cradle.start();
+        thread::sleep(Duration::from_millis(2500));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Ticks at 0 and 2 rock shard 0, the one at 1 rocks shard 1.
+        assert_eq!(even.load(Ordering::Relaxed), 2);
+        assert_eq!(odd.load(Ordering::Relaxed), 1);
+        assert_eq!(pinned.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_shards_visit() {
+        struct Checked(Arc<AtomicUsize>);
+        impl Baby for Checked {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                Ok(())
+            }
+            fn should_cry(&self, _elapsed: usize) -> bool {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+        }
+        let checks = Arc::new(AtomicUsize::new(0));
+        let mut core = (0..100)
+            .fold(Cradle::builder(), |builder, _| {
+                builder.baby(Checked(checks.clone()))
+            })
+            .shards(10)
+            .build_core();
+        let start = Instant::now();
+        core.apply(Command::Rock, start).unwrap();
+        // Only the babies of a single shard are looked at.
+        assert_eq!(checks.load(Ordering::Relaxed), 10);
+    }
+
+    #[test]
+    fn test_reset_burst() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Timeout(1))
+            .default_cry(counting_action(&count))
+            .build();
+        cradle.start();
+        let until = Instant::now() + Duration::from_millis(2500);
+        while Instant::now() < until {
+            for _ in 0..1000 {
+                cradle.reset();
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_watchdog() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let watchdog = Watchdog::new(Duration::from_millis(300), counting(&count));
+        for _ in 0..6 {
+            thread::sleep(Duration::from_millis(100));
+            watchdog.reset();
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        // Cries at 300ms and 600ms after the last reset.
+        thread::sleep(Duration::from_millis(750));
+        watchdog.stop();
+        watchdog.join().unwrap().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_watchdog_racing_resets() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let watchdog = Arc::new(Watchdog::new(Duration::ZERO, counting(&count)));
+        let resetter = thread::spawn({
+            let watchdog = watchdog.clone();
+            move || {
+                let start = Instant::now();
+                while start.elapsed() < Duration::from_millis(200) {
+                    watchdog.reset();
+                }
+            }
+        });
+        resetter.join().unwrap();
+        thread::sleep(Duration::from_millis(100));
+        let watchdog = Arc::into_inner(watchdog).unwrap();
+        watchdog.stop();
+        watchdog.join().unwrap().unwrap();
+        // Crying at most once per millisecond, not spinning.
+        assert!(count.load(Ordering::Relaxed) <= 300);
+    }
+
+    #[test]
+    fn test_static_cradle() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut cradle = StaticCradle::new([Patient(1, count.clone()), Patient(1, count.clone())]);
+        cradle.rock().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        thread::sleep(Duration::from_millis(1100));
+        cradle.reset_baby(1);
+        cradle.rock().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        cradle.reset();
+        cradle.rock().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        let mut cradle = StaticCradle::new([Timeout(0)]);
+        let err = cradle.rock().unwrap_err();
+        assert!(err.to_string().contains("without a cry"));
+    }
+
+    #[test]
+    fn test_cradle_core() {
+        // Never cries before its timeout, whatever the commands.
+        let mut core = Cradle::builder()
+            .baby(Timeout(2))
+            .default_cry(|_cry| Ok(()))
+            .build_core();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        core.apply(Command::Put(Box::new(Counter(Arc::default()))), at(0))
+            .unwrap();
+        let mut cries = vec![];
+        for millis in (0..6000).step_by(250) {
+            if millis == 3000 {
+                core.apply(Command::ResetBaby(0), at(millis)).unwrap();
+            }
+            for id in core.apply(Command::Rock, at(millis)).unwrap() {
+                cries.push((id, millis));
+            }
+        }
+        let first = |id| cries.iter().find(|cry| cry.0 == id).map(|cry| cry.1);
+        assert_eq!(first(0), Some(2000));
+        assert_eq!(first(1), Some(0));
+        assert!(!cries.contains(&(0, 4750)));
+        assert!(cries.contains(&(0, 5000)));
+        core.apply(Command::Remove(1), at(6000)).unwrap();
+        core.apply(Command::Mute, at(6000)).unwrap();
+        assert!(core.apply(Command::Rock, at(9000)).unwrap().is_empty());
+        assert_eq!(core.ids(), [0]);
+    }
+
+    #[test]
+    fn test_template() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let template = BabyTemplate::new(1)
+            .severity(Severity::Critical)
+            .label("team", "storage")
+            .cry({
+                let count = count.clone();
+                move |name| {
+                    assert!(name.starts_with("disk-"));
+                    counting(&count)
+                }
+            });
+        let baby = template.spawn("disk-0");
+        assert_eq!(baby.name(), "disk-0");
+        assert_eq!(baby.severity(), Severity::Critical);
+        let metadata = baby.metadata().unwrap();
+        let labels = metadata.downcast_ref::<Labels>().unwrap();
+        assert_eq!(labels.get("team"), Some("storage"));
+        let mut cradle = StaticCradle::new([baby, template.spawn("disk-1")]);
+        thread::sleep(Duration::from_millis(1100));
+        cradle.rock().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_status_delta() {
+        let cradle = Cradle::builder()
+            .baby(Counter(Arc::default()))
+            .silent()
+            .build();
+        assert_eq!(cradle.status_delta(0).cursor, 0);
+        cradle.start();
+        thread::sleep(Duration::from_millis(1500));
+        let delta = cradle.status_delta(0);
+        assert_eq!(delta.cursor, 2);
+        assert_eq!(
+            delta.events[0],
+            Event::WouldCry {
+                id: 0,
+                elapsed: 0,
+                metadata: None,
+            }
+        );
+        assert!(!delta.truncated);
+        let delta = cradle.status_delta(delta.cursor);
+        assert!(delta.events.is_empty());
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_cry_budget() {
+        struct Slow(Arc<AtomicUsize>);
+        impl Baby for Slow {
+            fn cry(&mut self, _elapsed: usize) -> BoxResult<()> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                thread::sleep(Duration::from_millis(200));
+                Ok(())
+            }
+        }
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Slow(count.clone()))
+            .cry_budget(Duration::from_millis(100), 2, Duration::from_secs(10))
+            .build();
+        let events = cradle.subscribe();
+        cradle.start();
+        let event = events.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert_eq!(event, Event::OverBudget(0));
+        thread::sleep(Duration::from_millis(1500));
+        assert!(cradle.cry_time(0).unwrap() >= Duration::from_millis(400));
+        assert_eq!(cradle.cry_time(1), None);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Muted after two slow cries.
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_state_events() {
+        let cradle = Cradle::builder()
+            .baby(Timeout(2))
+            .baby(Timeout(10))
+            .consecutive(2)
+            .default_cry(|_cry| Ok(()))
+            .state_events()
+            .build();
+        let events = cradle.subscribe();
+        cradle.start();
+        let changed = |from, to, reason| Event::StateChanged {
+            id: 0,
+            from,
+            to,
+            reason,
+            metadata: None,
+        };
+        let event = events.recv_timeout(Duration::from_secs(3)).unwrap();
+        let late = changed(BabyState::Sleeping, BabyState::Late, Reason::TimeoutElapsed);
+        assert_eq!(event, late);
+        let event = events.recv_timeout(Duration::from_secs(3)).unwrap();
+        let crying = changed(BabyState::Late, BabyState::Crying, Reason::TimeoutElapsed);
+        assert_eq!(event, crying);
+        cradle.reset_baby(0);
+        let event = events.recv_timeout(Duration::from_secs(3)).unwrap();
+        let soothed = changed(BabyState::Crying, BabyState::Soothed, Reason::ResetReceived);
+        assert_eq!(event, soothed);
+        let event = events.recv_timeout(Duration::from_secs(3)).unwrap();
+        let sleeping = changed(BabyState::Soothed, BabyState::Sleeping, Reason::Recovered);
+        assert_eq!(event, sleeping);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_observer() {
+        let cradle = Cradle::builder()
+            .baby(Timeout(0))
+            .baby(Timeout(100))
+            .silent()
+            .build();
+        let observer = cradle.observer();
+        let events = observer.subscribe();
+        cradle.start();
+        let event = events.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(
+            event,
+            Event::WouldCry {
+                id: 0,
+                elapsed: 0,
+                metadata: None,
+            }
+        );
+        assert!(observer.next_deadline().is_some());
+        assert_eq!(observer.predict(Duration::from_secs(10)).len(), 1);
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        assert_eq!(observer.status_delta(0).cursor, 0);
+    }
+
+    #[test]
+    fn test_grace() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::builder()
+            .baby(Timeout(1))
+            .default_cry(counting_action(&count))
+            .grace(Duration::from_millis(1500))
+            .build();
+        let start = Instant::now();
+        cradle.start();
+        thread::sleep(Duration::from_millis(100));
+        // Due at 1, but held back until the grace period ends.
+        assert!(cradle.predict(Duration::from_secs(1)).is_empty());
+        thread::sleep(Duration::from_millis(1100));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        assert!(cradle
+            .next_deadline()
+            .is_some_and(|due| due >= start + Duration::from_millis(1500)));
+        thread::sleep(Duration::from_millis(1000));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Only the tick at 2 is past the grace period.
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[cfg(feature = "chaos")]
+    #[test]
+    fn test_chaos() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let cradle = Cradle::new(vec![Patient(2, count.clone())]);
+        let chaos = cradle.chaos();
+        chaos.jump_clock(Duration::from_secs(5));
+        chaos.drop_resets(1);
+        cradle.start();
+        thread::sleep(Duration::from_millis(1200));
+        cradle.reset_baby(0);
+        thread::sleep(Duration::from_millis(1300));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+        // Aged by the jump, the baby cries on every tick, its reset being dropped.
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[cfg(feature = "chaos")]
+    #[test]
+    fn test_chaos_ttl() {
+        let cradle = Cradle::new(vec![Ephemeral(60)]);
+        let events = cradle.subscribe();
+        cradle.chaos().jump_clock(Duration::from_secs(120));
+        cradle.start();
+        // Aged past its ttl by the jump, the baby expires on the first tick.
+        let event = events.recv_timeout(Duration::from_millis(500)).unwrap();
+        assert_eq!(event, Event::Expired(0));
+        cradle.stop();
+        cradle.join().unwrap().unwrap();
+    }
 }
@@ -0,0 +1,82 @@
+use super::{BabyId, Cradle, Delta, Event, Resets, Signal, Status};
+use std::{
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// A read-only handle on a cradle, see [`Cradle::observer`].
+///
+/// It can tell the cradle's status and subscribe to its events, but neither reset,
+/// make babies cry nor stop the cradle, so it is safe to hand to dashboards.
+/// It can be cloned and sent to other threads.
+#[derive(Clone)]
+pub struct CradleObserver {
+    pub(super) tx: Sender<Signal>,
+    pub(super) status: Arc<Mutex<Status>>,
+    pub(super) resets: Arc<Resets>,
+}
+
+impl CradleObserver {
+    /// See [`Cradle::elapsed`].
+    pub fn elapsed(&self) -> Duration {
+        let status = Status::lock(&self.status);
+        status
+            .reset_at
+            .map(|at| at.max(self.resets.last().unwrap_or(at)).elapsed())
+            .unwrap_or_default()
+    }
+
+    /// See [`Cradle::next_deadline`].
+    pub fn next_deadline(&self) -> Option<Instant> {
+        Status::lock(&self.status).next_deadline
+    }
+
+    /// See [`Cradle::predict`], nothing is due once the cradle is gone.
+    pub fn predict(&self, horizon: Duration) -> Vec<(BabyId, Instant)> {
+        let (tx, rx) = channel();
+        let _ = self.tx.send(Signal::Predict(horizon, tx));
+        rx.recv().unwrap_or_default()
+    }
+
+    /// See [`Cradle::last_seen`].
+    pub fn last_seen(&self, id: BabyId) -> Option<Vec<(&'static str, Option<Instant>)>> {
+        let (tx, rx) = channel();
+        self.tx.send(Signal::LastSeen(id, tx)).ok()?;
+        rx.recv().ok()
+    }
+
+    /// See [`Cradle::cry_time`].
+    pub fn cry_time(&self, id: BabyId) -> Option<Duration> {
+        let (tx, rx) = channel();
+        self.tx.send(Signal::CryTime(id, tx)).ok()?;
+        rx.recv().ok()
+    }
+
+    /// See [`Cradle::status_delta`].
+    pub fn status_delta(&self, cursor: usize) -> Delta {
+        let (tx, rx) = channel();
+        let _ = self.tx.send(Signal::Delta(cursor, tx));
+        rx.recv().unwrap_or(Delta {
+            cursor,
+            events: vec![],
+            truncated: false,
+        })
+    }
+
+    /// See [`Cradle::subscribe`], the receiver is disconnected once the cradle is gone.
+    pub fn subscribe(&self) -> Receiver<Event> {
+        let (tx, rx) = channel();
+        let _ = self.tx.send(Signal::Subscribe(tx));
+        rx
+    }
+}
+
+impl Cradle {
+    /// A read-only handle on the cradle.
+    pub fn observer(&self) -> CradleObserver {
+        self.observer.clone()
+    }
+}
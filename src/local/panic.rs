@@ -0,0 +1,64 @@
+use super::{BabyId, BoxResult, Cradle, Signal};
+use std::{
+    error::Error,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc::channel,
+    thread,
+    time::Duration,
+};
+
+impl Cradle {
+    /// Installs a panic hook making the baby `id` cry as soon as any thread panics,
+    /// before unwinding or aborting, instead of waiting for its heartbeats to lapse.
+    ///
+    /// The panic message, a [`String`], is the [`CryContext::metadata`](super::CryContext::metadata)
+    /// of the cry. The previous hook still runs afterwards.
+    ///
+    /// Panics of the cradle's own callbacks do not make the baby cry, being caught
+    /// and reported as [`Event::Panicked`](super::Event::Panicked) already.
+    pub fn cry_on_panic(&self, id: BabyId) {
+        let tx = self.tx.clone();
+        let cradle = self.jh.thread().id();
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // The cradle thread cannot answer itself, it would only wait for nothing.
+            if thread::current().id() == cradle {
+                return previous(info);
+            }
+            let (done, cried) = channel();
+            if tx.send(Signal::Panic(id, info.to_string(), done)).is_ok() {
+                // The cradle itself may be panicking, do not wait forever.
+                let _ = cried.recv_timeout(Duration::from_secs(1));
+            }
+            previous(info);
+        }));
+    }
+}
+
+/// A user callback panicked, see [`Event::Panicked`](super::Event::Panicked).
+#[derive(Debug)]
+pub(super) struct Panicked(String);
+
+impl fmt::Display for Panicked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "callback panicked: {}", self.0)
+    }
+}
+
+impl Error for Panicked {}
+
+/// Runs a user callback, turning a panic into a [`Panicked`] error.
+pub(super) fn catch<F>(callback: F) -> BoxResult<()>
+where
+    F: FnOnce() -> BoxResult<()>,
+{
+    panic::catch_unwind(AssertUnwindSafe(callback)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(Box::new(Panicked(message)))
+    })
+}
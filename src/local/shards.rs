@@ -0,0 +1,176 @@
+use super::{worker::Slot, BabyId};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+    time::Instant,
+};
+
+/// The babies of a cradle, spread over shards, see [`CradleBuilder::shards`](super::CradleBuilder::shards).
+///
+/// A tick only visits the babies of one shard, critical ones aside, and deadlines are kept sorted,
+/// so that neither grows with the whole cradle.
+/// Callers changing what a baby's deadline depends on must [`Shards::refresh`] it.
+pub(super) struct Shards {
+    entries: BTreeMap<BabyId, Entry>,
+    /// The non-critical babies of each shard, in the order they were put in.
+    buckets: Vec<Vec<BabyId>>,
+    /// Critical babies, rocked on every tick.
+    critical: Vec<BabyId>,
+    /// Every baby's deadline.
+    deadlines: BTreeSet<(Instant, BabyId)>,
+    /// Critical babies' deadlines.
+    critical_deadlines: BTreeSet<(Instant, BabyId)>,
+}
+
+struct Entry {
+    slot: Slot,
+    /// The shard of a non-critical baby.
+    shard: Option<usize>,
+    /// The deadline last recorded for the baby.
+    due: Option<Instant>,
+}
+
+impl Shards {
+    pub(super) fn new(shards: usize) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            buckets: vec![vec![]; shards.max(1)],
+            critical: vec![],
+            deadlines: BTreeSet::new(),
+            critical_deadlines: BTreeSet::new(),
+        }
+    }
+
+    pub(super) fn insert(&mut self, slot: Slot) {
+        let id = slot.id;
+        self.remove(id);
+        let shard = (!slot.critical()).then(|| slot.shard() % self.buckets.len());
+        match shard {
+            Some(shard) => self.buckets[shard].push(id),
+            None => self.critical.push(id),
+        }
+        self.entries.insert(
+            id,
+            Entry {
+                slot,
+                shard,
+                due: None,
+            },
+        );
+        self.refresh(id);
+    }
+
+    pub(super) fn remove(&mut self, id: BabyId) -> Option<Slot> {
+        let entry = self.entries.remove(&id)?;
+        let home = match entry.shard {
+            Some(shard) => &mut self.buckets[shard],
+            None => &mut self.critical,
+        };
+        home.retain(|other| *other != id);
+        if let Some(due) = entry.due {
+            self.deadlines.remove(&(due, id));
+            self.critical_deadlines.remove(&(due, id));
+        }
+        Some(entry.slot)
+    }
+
+    pub(super) fn get(&self, id: BabyId) -> Option<&Slot> {
+        self.entries.get(&id).map(|entry| &entry.slot)
+    }
+
+    /// The baby `id`, to [`Shards::refresh`] after changing its deadline.
+    pub(super) fn get_mut(&mut self, id: BabyId) -> Option<&mut Slot> {
+        self.entries.get_mut(&id).map(|entry| &mut entry.slot)
+    }
+
+    /// Changes the baby `id`, refreshing its deadline.
+    pub(super) fn update<R>(&mut self, id: BabyId, f: impl FnOnce(&mut Slot) -> R) -> Option<R> {
+        let changed = f(self.get_mut(id)?);
+        self.refresh(id);
+        Some(changed)
+    }
+
+    /// Changes every baby, refreshing their deadlines.
+    pub(super) fn update_all(&mut self, mut f: impl FnMut(&mut Slot)) {
+        self.entries
+            .values_mut()
+            .for_each(|entry| f(&mut entry.slot));
+        self.deadlines.clear();
+        self.critical_deadlines.clear();
+        for (&id, entry) in self.entries.iter_mut() {
+            entry.due = entry.slot.deadline(None);
+            if let Some(due) = entry.due {
+                self.deadlines.insert((due, id));
+                if entry.shard.is_none() {
+                    self.critical_deadlines.insert((due, id));
+                }
+            }
+        }
+    }
+
+    /// Records the current deadline of the baby `id`.
+    pub(super) fn refresh(&mut self, id: BabyId) {
+        let Some(entry) = self.entries.get_mut(&id) else {
+            return;
+        };
+        let due = entry.slot.deadline(None);
+        if due == entry.due {
+            return;
+        }
+        let critical = entry.shard.is_none();
+        if let Some(old) = std::mem::replace(&mut entry.due, due) {
+            self.deadlines.remove(&(old, id));
+            self.critical_deadlines.remove(&(old, id));
+        }
+        if let Some(due) = due {
+            self.deadlines.insert((due, id));
+            if critical {
+                self.critical_deadlines.insert((due, id));
+            }
+        }
+    }
+
+    /// The babies to rock on the `tick`th tick, only critical ones if `critical_only`.
+    pub(super) fn rocked(&self, tick: usize, critical_only: bool) -> Vec<BabyId> {
+        let mut ids = self.critical.clone();
+        if !critical_only {
+            ids.extend(&self.buckets[tick % self.buckets.len()]);
+        }
+        ids
+    }
+
+    /// Every baby, by id.
+    pub(super) fn iter(&self) -> impl Iterator<Item = &Slot> {
+        self.entries.values().map(|entry| &entry.slot)
+    }
+
+    /// Every baby by id, not to change their deadlines, see [`Shards::update_all`].
+    pub(super) fn iter_mut(&mut self) -> impl Iterator<Item = &mut Slot> {
+        self.entries.values_mut().map(|entry| &mut entry.slot)
+    }
+
+    pub(super) fn ids(&self) -> Vec<BabyId> {
+        self.entries.keys().copied().collect()
+    }
+
+    /// The earliest deadline.
+    pub(super) fn next_deadline(&self) -> Option<Instant> {
+        self.deadlines.first().map(|&(due, _)| due)
+    }
+
+    /// The earliest deadline of a critical baby, after `last` if given.
+    pub(super) fn critical_deadline(&self, last: Option<Instant>) -> Option<Instant> {
+        let after = last.map_or(Bound::Unbounded, |last| {
+            Bound::Excluded((last, BabyId::MAX))
+        });
+        self.critical_deadlines
+            .range((after, Bound::Unbounded))
+            .next()
+            .map(|&(due, _)| due)
+    }
+
+    /// Every deadline, earliest first.
+    pub(super) fn deadlines(&self) -> impl Iterator<Item = (BabyId, Instant)> + '_ {
+        self.deadlines.iter().map(|&(due, id)| (id, due))
+    }
+}
@@ -0,0 +1,126 @@
+use super::{Baby, BoxResult, Metadata, Severity};
+use std::sync::Arc;
+
+/// The cry of a baby spawned from a [`BabyTemplate`].
+type Cry = Box<dyn FnMut(usize) -> BoxResult<()> + Send>;
+
+/// Makes the cry of each spawned baby from its name.
+type CryFactory = Arc<dyn Fn(&str) -> Cry + Send + Sync>;
+
+/// A blueprint of similar babies, for fleets of monitors defined programmatically.
+///
+/// Spawned babies share the template's timeout, severity and labels,
+/// each getting its own cry from the template's factory.
+#[derive(Clone)]
+pub struct BabyTemplate {
+    timeout: usize,
+    severity: Severity,
+    labels: Vec<(String, String)>,
+    cry: Option<CryFactory>,
+}
+
+/// The metadata of a baby spawned from a [`BabyTemplate`], see [`Baby::metadata`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Labels {
+    /// The name given to [`BabyTemplate::spawn`].
+    pub name: String,
+    /// The template's labels, in order.
+    pub labels: Vec<(String, String)>,
+}
+
+impl Labels {
+    /// The value of the first label named `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A baby spawned from a [`BabyTemplate`].
+pub struct Spawned {
+    timeout: usize,
+    severity: Severity,
+    labels: Arc<Labels>,
+    cry: Option<Cry>,
+}
+
+impl BabyTemplate {
+    /// A template of babies crying once `timeout` seconds have elapsed.
+    ///
+    /// Without [`BabyTemplate::cry`], they cry through
+    /// [`CradleBuilder::default_cry`](super::CradleBuilder::default_cry).
+    pub fn new(timeout: usize) -> Self {
+        Self {
+            timeout,
+            severity: Severity::Warning,
+            labels: vec![],
+            cry: None,
+        }
+    }
+
+    /// Sets the severity of spawned babies, see [`Baby::severity`].
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Adds a label to spawned babies, carried in their [`Labels`].
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.push((key.into(), value.into()));
+        self
+    }
+
+    /// Makes the cry of each spawned baby by calling `factory` with its name.
+    pub fn cry<F, C>(mut self, factory: F) -> Self
+    where
+        F: Fn(&str) -> C + Send + Sync + 'static,
+        C: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        self.cry = Some(Arc::new(move |name| Box::new(factory(name))));
+        self
+    }
+
+    /// Stamps out a baby named `name`.
+    pub fn spawn(&self, name: &str) -> Spawned {
+        Spawned {
+            timeout: self.timeout,
+            severity: self.severity,
+            labels: Arc::new(Labels {
+                name: name.to_owned(),
+                labels: self.labels.clone(),
+            }),
+            cry: self.cry.as_ref().map(|factory| factory(name)),
+        }
+    }
+}
+
+impl Spawned {
+    /// The name given to [`BabyTemplate::spawn`].
+    pub fn name(&self) -> &str {
+        &self.labels.name
+    }
+}
+
+impl Baby for Spawned {
+    fn cry(&mut self, elapsed: usize) -> BoxResult<()> {
+        self.cry.as_mut().map_or(Ok(()), |cry| cry(elapsed))
+    }
+
+    fn has_cry(&self) -> bool {
+        self.cry.is_some()
+    }
+
+    fn timeout(&self) -> Option<usize> {
+        Some(self.timeout)
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn metadata(&self) -> Option<Metadata> {
+        Some(self.labels.clone())
+    }
+}
@@ -0,0 +1,90 @@
+use super::BoxResult;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The shortest timeout of a [`Watchdog`].
+const MIN_TIMEOUT: Duration = Duration::from_millis(1);
+
+/// A minimal watchdog for a single deadline and action, without the machinery of a [`Cradle`](super::Cradle).
+///
+/// It cries every `timeout` until reset, resetting neither allocates nor locks.
+pub struct Watchdog {
+    shared: Arc<Shared>,
+    jh: thread::JoinHandle<BoxResult<()>>,
+}
+
+struct Shared {
+    base: Instant,
+    /// When the watchdog was last reset, in nanoseconds since `base`.
+    last: AtomicU64,
+    stopped: AtomicBool,
+}
+
+impl Shared {
+    fn now(&self) -> u64 {
+        self.base.elapsed().as_nanos() as u64
+    }
+}
+
+impl Watchdog {
+    /// Starts a watchdog calling `cry` with the elapsed time in seconds once `timeout` has passed
+    /// without a reset.
+    ///
+    /// `timeout` is at least a millisecond, so that the watchdog does not spin.
+    /// The watchdog stops at the first error, returned by [`Watchdog::join`].
+    pub fn new<F>(timeout: Duration, mut cry: F) -> Self
+    where
+        F: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        let shared = Arc::new(Shared {
+            base: Instant::now(),
+            last: AtomicU64::new(0),
+            stopped: AtomicBool::new(false),
+        });
+        let jh = thread::spawn({
+            let shared = shared.clone();
+            move || {
+                let timeout = timeout.max(MIN_TIMEOUT).as_nanos() as u64;
+                let mut cried = 0;
+                while !shared.stopped.load(Ordering::Acquire) {
+                    // Loaded once, before `now`, so that a concurrent reset cannot be newer.
+                    let last = shared.last.load(Ordering::Acquire);
+                    let now = shared.now();
+                    // Cry again every timeout until reset.
+                    let due = last.max(cried) + timeout;
+                    if now < due {
+                        thread::park_timeout(Duration::from_nanos(due - now));
+                        continue;
+                    }
+                    cried = now;
+                    let elapsed = now.saturating_sub(last);
+                    cry(Duration::from_nanos(elapsed).as_secs() as usize)?;
+                }
+                Ok(())
+            }
+        });
+        Self { shared, jh }
+    }
+
+    /// Resets the watchdog's elapsed time, so that it will not cry.
+    pub fn reset(&self) {
+        self.shared.last.store(self.shared.now(), Ordering::Release);
+    }
+
+    /// Stops the watchdog.
+    pub fn stop(&self) {
+        self.shared.stopped.store(true, Ordering::Release);
+        self.jh.thread().unpark();
+    }
+
+    /// Joins the watchdog thread.
+    pub fn join(self) -> thread::Result<BoxResult<()>> {
+        self.jh.join()
+    }
+}
@@ -0,0 +1,224 @@
+use super::{
+    heartbeat::{Cry, Stall},
+    Baby, BabyId, BoxResult, Cradle, Heartbeat, Signal,
+};
+use std::{
+    fmt,
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, LockResult, Mutex, MutexGuard, PoisonError,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// A [`Mutex`] watched by a baby, which cries once the lock has been held
+/// or waited on for `threshold` seconds, e.g. when threads deadlock.
+pub struct WatchedMutex<T> {
+    inner: Mutex<T>,
+    heartbeat: Heartbeat,
+    watch: Arc<Watch>,
+}
+
+/// The guard of a [`WatchedMutex`], restarting the watch when released.
+pub struct WatchedMutexGuard<'a, T> {
+    inner: MutexGuard<'a, T>,
+    mutex: &'a WatchedMutex<T>,
+    /// When the hold began, see [`Watch::since`].
+    since: u64,
+}
+
+impl<T> WatchedMutex<T> {
+    /// Wraps `value`, putting its watching baby into `cradle`, which calls `cry`
+    /// with its elapsed time.
+    pub fn new<C>(cradle: &Cradle, value: T, threshold: usize, cry: C) -> Self
+    where
+        C: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        let watch = Arc::new(Watch {
+            base: Instant::now(),
+            busy: AtomicUsize::new(0),
+            since: AtomicU64::new(0),
+        });
+        let id = cradle.put_baby(Lock {
+            threshold: Duration::from_secs(threshold as u64),
+            watch: watch.clone(),
+            cry: Box::new(cry),
+        });
+        Self {
+            inner: Mutex::new(value),
+            heartbeat: cradle.heartbeat(id),
+            watch,
+        }
+    }
+
+    /// The baby watching the lock.
+    pub fn id(&self) -> BabyId {
+        self.heartbeat.id()
+    }
+
+    /// Acquires the lock like [`Mutex::lock`], the wait being watched as well.
+    pub fn lock(&self) -> LockResult<WatchedMutexGuard<'_, T>> {
+        let watch = &self.watch;
+        // Idle time does not count, only the first waiter starts the watch.
+        let _ = watch
+            .since
+            .compare_exchange(0, watch.now(), Ordering::SeqCst, Ordering::SeqCst);
+        watch.busy.fetch_add(1, Ordering::SeqCst);
+        let guard = |inner| {
+            let since = watch.now();
+            watch.since.store(since, Ordering::SeqCst);
+            WatchedMutexGuard {
+                inner,
+                mutex: self,
+                since,
+            }
+        };
+        self.inner
+            .lock()
+            .map(guard)
+            .map_err(|e| PoisonError::new(guard(e.into_inner())))
+    }
+}
+
+impl<T> Drop for WatchedMutex<T> {
+    fn drop(&mut self) {
+        let _ = self.heartbeat.tx.send(Signal::Remove(self.id()));
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for WatchedMutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchedMutex")
+            .field("id", &self.id())
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T> Deref for WatchedMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for WatchedMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T> Drop for WatchedMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        let watch = &self.mutex.watch;
+        // Stop watching once the lock is free, unless a waiter acquired it meanwhile.
+        if watch.busy.fetch_sub(1, Ordering::SeqCst) == 1 {
+            let _ = watch
+                .since
+                .compare_exchange(self.since, 0, Ordering::SeqCst, Ordering::SeqCst);
+        }
+    }
+}
+
+/// A [`Future`] watched by a baby, which cries once it has not been polled for
+/// `timeout` seconds, unless it reports progress through [`WatchedFuture::heartbeat`],
+/// see [`Cradle::watch`].
+///
+/// The baby is removed once the future completes or is dropped.
+pub struct WatchedFuture<F> {
+    inner: Pin<Box<F>>,
+    heartbeat: Heartbeat,
+}
+
+impl<F> WatchedFuture<F> {
+    /// The baby watching the future.
+    pub fn id(&self) -> BabyId {
+        self.heartbeat.id()
+    }
+
+    /// A heartbeat for the future to mark progress while it is not yielding.
+    pub fn heartbeat(&self) -> Heartbeat {
+        self.heartbeat.clone()
+    }
+}
+
+impl<F: Future> Future for WatchedFuture<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        self.heartbeat.beat();
+        let poll = self.inner.as_mut().poll(cx);
+        if poll.is_ready() {
+            let _ = self.heartbeat.tx.send(Signal::Remove(self.id()));
+        }
+        poll
+    }
+}
+
+impl<F> Drop for WatchedFuture<F> {
+    fn drop(&mut self) {
+        let _ = self.heartbeat.tx.send(Signal::Remove(self.id()));
+    }
+}
+
+impl Cradle {
+    /// Watches `future` for stalls, e.g. a task starved by its executor.
+    ///
+    /// Works with any executor, the baby calling `cry` with its elapsed time.
+    pub fn watch<F, C>(&self, future: F, timeout: usize, cry: C) -> WatchedFuture<F>
+    where
+        F: Future,
+        C: FnMut(usize) -> BoxResult<()> + Send + 'static,
+    {
+        let id = self.put_baby(Stall {
+            timeout,
+            cry: Box::new(cry),
+        });
+        WatchedFuture {
+            inner: Box::pin(future),
+            heartbeat: self.heartbeat(id),
+        }
+    }
+}
+
+/// The state of a [`WatchedMutex`], shared with its baby.
+struct Watch {
+    base: Instant,
+    /// Threads holding or waiting on the lock.
+    busy: AtomicUsize,
+    /// When the current hold, or the wait of the first waiter on the free lock, began,
+    /// in nanoseconds since `base`, 0 while the lock is free.
+    since: AtomicU64,
+}
+
+impl Watch {
+    /// The current time, in nanoseconds since `base`, never 0.
+    fn now(&self) -> u64 {
+        (self.base.elapsed().as_nanos() as u64).max(1)
+    }
+}
+
+/// Cries while its lock is held or waited on for too long.
+///
+/// It has no timeout, since it cannot cry while the lock is free.
+struct Lock {
+    threshold: Duration,
+    watch: Arc<Watch>,
+    cry: Cry,
+}
+
+impl Baby for Lock {
+    fn cry(&mut self, elapsed: usize) -> BoxResult<()> {
+        (self.cry)(elapsed)
+    }
+
+    fn should_cry(&self, _elapsed: usize) -> bool {
+        let since = self.watch.since.load(Ordering::SeqCst);
+        since != 0 && Duration::from_nanos(self.watch.now().saturating_sub(since)) >= self.threshold
+    }
+}
@@ -0,0 +1,1130 @@
+use super::{
+    builder::{Action, Batch, CradleBuilder, Digest, ErrorHandler, Farewell, HealthReport},
+    later,
+    panic::{catch, Panicked},
+    shards::Shards,
+    Baby, BabyId, BabyState, BoxResult, CryContext, Delta, ErrorSource, Event, EventMetadata,
+    Health, Metadata, Rate, Reason, Resets, Severity, Signal, Status,
+};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt,
+    sync::{
+        atomic::Ordering,
+        mpsc::{Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex, MutexGuard,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// The cradle thread, rocking babies once per tick.
+pub(super) struct Worker {
+    rx: Receiver<Signal>,
+    slots: Shards,
+    align: Option<Duration>,
+    muted: bool,
+    rate_limit: Option<RateLimit>,
+    dedup: Option<Dedup>,
+    consecutive: usize,
+    severity_actions: Vec<(Severity, Action)>,
+    default_cry: Option<Action>,
+    batch: Option<(usize, Batch)>,
+    health: Option<Reporter>,
+    silent: bool,
+    farewell: Option<Farewell>,
+    on_error: Option<ErrorHandler>,
+    ticker: Option<Ticker>,
+    state_events: bool,
+    cry_budget: Option<CryBudget>,
+    /// See [`CradleBuilder::grace`].
+    grace: Duration,
+    /// Full ticks so far, telling which shard to rock.
+    ticks: usize,
+    subscribers: Vec<Sender<Event>>,
+    /// The latest events, for [`Cradle::status_delta`](super::Cradle::status_delta).
+    journal: VecDeque<Event>,
+    /// How many events were emitted so far.
+    emitted: usize,
+    status: Arc<Mutex<Status>>,
+    resets: Arc<Resets>,
+    /// The resets caught up with so far.
+    seen: usize,
+    started_at: Option<Instant>,
+    /// Babies which cried, recorded for [`CradleCore::apply`](super::CradleCore::apply).
+    cried: Option<Vec<BabyId>>,
+    #[cfg(feature = "chaos")]
+    chaos: super::Chaos,
+}
+
+impl Worker {
+    pub(super) fn new(
+        rx: Receiver<Signal>,
+        builder: CradleBuilder,
+        status: Arc<Mutex<Status>>,
+        resets: Arc<Resets>,
+    ) -> Self {
+        let now = Instant::now();
+        let mut slots = Shards::new(builder.shards.unwrap_or(1));
+        for (id, baby) in builder.babies.into_iter().enumerate() {
+            slots.insert(Slot::new(id, baby, now));
+        }
+        Self {
+            rx,
+            slots,
+            align: builder.align,
+            muted: false,
+            rate_limit: builder
+                .rate_limit
+                .map(|(max, digest)| RateLimit::new(max, digest)),
+            dedup: builder.dedup.map(Dedup::new),
+            consecutive: builder.consecutive,
+            severity_actions: builder.severity_actions,
+            default_cry: builder.default_cry,
+            batch: builder.batch,
+            health: builder.health_report.map(|(period, handler)| Reporter {
+                period,
+                handler,
+                last: Instant::now(),
+                cries: 0,
+            }),
+            silent: builder.silent,
+            farewell: builder.farewell,
+            on_error: builder.on_error,
+            ticker: builder.ticker.map(|(every, ids)| Ticker {
+                every,
+                ids,
+                last: None,
+            }),
+            state_events: builder.state_events,
+            grace: builder.grace,
+            cry_budget: builder.cry_budget.map(|(budget, strikes, mute)| CryBudget {
+                budget,
+                strikes,
+                mute,
+            }),
+            ticks: 0,
+            subscribers: Vec::new(),
+            journal: VecDeque::new(),
+            emitted: 0,
+            status,
+            resets,
+            seen: 0,
+            started_at: None,
+            cried: None,
+            #[cfg(feature = "chaos")]
+            chaos: builder.chaos,
+        }
+    }
+
+    pub(super) fn run(mut self) -> BoxResult<()> {
+        // Babies may be put into the cradle before it starts.
+        loop {
+            match self.rx.recv() {
+                Ok(Signal::Start) => break,
+                // Never started, nothing to say goodbye to.
+                Ok(Signal::Stop) | Err(_) => return Ok(()),
+                Ok(signal) => self.handle(signal, Instant::now())?,
+            }
+        }
+        let now = Instant::now();
+        self.start(now);
+        let mut next = now;
+        let mut last = now;
+        let mut disconnected = false;
+        loop {
+            // Critical babies do not wait for the next tick.
+            let wake = self
+                .critical_deadline(last)
+                .map_or(next, |due| due.min(next));
+            let timeout = wake.saturating_duration_since(Instant::now());
+            match self.rx.recv_timeout(timeout) {
+                Ok(Signal::Stop) => break,
+                Ok(signal) => self.handle(signal, Instant::now())?,
+                Err(e) => {
+                    // Keep rocking even if the cradle handle has been dropped.
+                    if e == RecvTimeoutError::Disconnected {
+                        if let Some(on_error) = self.on_error.as_mut().filter(|_| !disconnected) {
+                            on_error(&e, ErrorSource::Disconnected);
+                        }
+                        disconnected = true;
+                        thread::sleep(timeout);
+                    }
+                    let now = Instant::now();
+                    last = now;
+                    if now < next {
+                        self.tick(now, true)?;
+                        continue;
+                    }
+                    self.rock(now)?;
+                    next = self.next_tick(now);
+                }
+            }
+        }
+        self.status().stopped = true;
+        self.goodbye()
+    }
+
+    /// Starts watching babies at `now`.
+    pub(super) fn start(&mut self, now: Instant) {
+        self.started_at = Some(now);
+        // Resets before the start are moot.
+        self.seen = self.resets.seq.load(Ordering::Acquire);
+        let period = match self.align {
+            None => Duration::from_secs(1),
+            Some(boundary) => Duration::from_secs(boundary.as_secs().max(1)),
+        };
+        {
+            let mut status = self.status();
+            status.last_tick = Some(now);
+            status.period = period;
+        }
+        self.reset(now);
+        if let Some(rate_limit) = self.rate_limit.as_mut() {
+            rate_limit.window = now;
+        }
+        if let Some(health) = self.health.as_mut() {
+            health.last = now;
+        }
+    }
+
+    /// Whether the cradle started watching babies.
+    pub(super) fn started(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    /// Rocks every baby due on the tick at `now`.
+    pub(super) fn rock(&mut self, now: Instant) -> BoxResult<()> {
+        self.tick(now, false)?;
+        self.ticks += 1;
+        self.status().last_tick = Some(now);
+        self.publish();
+        Ok(())
+    }
+
+    /// Records the babies which cry from now on, see [`Worker::take_cried`].
+    pub(super) fn record_cries(&mut self) {
+        self.cried.get_or_insert_with(Vec::new);
+    }
+
+    /// The babies which cried since the last call, in order.
+    pub(super) fn take_cried(&mut self) -> Vec<BabyId> {
+        self.cried.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// The ids of the babies in the cradle, in order.
+    pub(super) fn ids(&self) -> Vec<BabyId> {
+        self.slots.ids()
+    }
+
+    /// Rocks babies, critical ones first, or only them if `critical_only`.
+    fn tick(&mut self, now: Instant, critical_only: bool) -> BoxResult<()> {
+        self.catch_up();
+        #[cfg(feature = "chaos")]
+        {
+            let jump = self.chaos.take_jump();
+            if !jump.is_zero() {
+                self.started_at = self.started_at.map(|at| at.checked_sub(jump).unwrap_or(at));
+                self.slots.update_all(|slot| slot.age(jump));
+            }
+        }
+        let mut ids = self.slots.rocked(self.ticks, critical_only);
+        // Babies whose cries were dropped by the rate limit go first among their severity.
+        ids.sort_by_cached_key(|&id| {
+            self.slots
+                .get(id)
+                .map(|slot| (Reverse(slot.baby.severity()), Reverse(slot.starved)))
+        });
+        if let Some(rate_limit) = self.rate_limit.as_mut() {
+            if let Some(dropped) = rate_limit.roll(now).filter(|_| !self.silent) {
+                let result = catch(|| (rate_limit.digest)(dropped));
+                self.report(result, ErrorSource::Digest)?;
+            }
+        }
+        let (ids, expired): (Vec<_>, Vec<_>) = ids.into_iter().partition(|&id| {
+            self.slots.get(id).is_some_and(|slot| {
+                slot.baby.ttl().is_none_or(|ttl| {
+                    now.saturating_duration_since(slot.born) < Duration::from_secs(ttl as u64)
+                })
+            })
+        });
+        for &id in &expired {
+            self.slots.remove(id);
+        }
+        if let Some(dedup) = self.dedup.as_mut() {
+            dedup.prune(now);
+            expired.iter().for_each(|&id| dedup.forget(id));
+        }
+        for id in expired {
+            self.emit(Event::Expired(id));
+        }
+        let warming = self
+            .started_at
+            .is_some_and(|at| now.saturating_duration_since(at) < self.grace);
+        let mut unmuted = vec![];
+        let mut changes = vec![];
+        let mut cries = vec![];
+        let mut muted = vec![];
+        for &id in &ids {
+            let Some(slot) = self.slots.get_mut(id) else {
+                continue;
+            };
+            let critical = slot.critical();
+            if slot.muted_until.is_some_and(|until| until <= now) {
+                slot.muted_until = None;
+                unmuted.push(slot.id);
+            }
+            if warming {
+                if matches!(slot.state, BabyState::Late | BabyState::Crying) {
+                    changes.extend(slot.enter(BabyState::Soothed, Reason::Grace));
+                }
+                continue;
+            }
+            let elapsed = slot.elapsed(now);
+            let started_at = self.started_at.unwrap_or(now);
+            let mut wants = if let Some(rate) = slot.baby.rate() {
+                slot.below(rate, now, started_at)
+            } else if !slot.baby.sources().is_empty() {
+                !slot.quorate(now, started_at)
+            } else {
+                slot.baby.should_cry(elapsed)
+            };
+            // Spend a banked reset instead of crying.
+            if wants && slot.credit > 0 {
+                slot.credit -= 1;
+                slot.since = now;
+                wants = false;
+            }
+            if !wants {
+                slot.overdue = 0;
+                let settled = match slot.state {
+                    BabyState::Sleeping | BabyState::Soothed => BabyState::Sleeping,
+                    BabyState::Late | BabyState::Crying => BabyState::Soothed,
+                };
+                changes.extend(slot.enter(settled, Reason::Recovered));
+                continue;
+            }
+            if matches!(slot.state, BabyState::Sleeping | BabyState::Soothed) {
+                changes.extend(slot.enter(BabyState::Late, Reason::TimeoutElapsed));
+            }
+            if slot.overdue == 0 {
+                slot.flaps += 1;
+            }
+            slot.overdue += 1;
+            if slot.overdue < self.consecutive {
+                continue;
+            }
+            // Muted babies are evaluated as usual, only their cries are held back.
+            if self.muted || slot.muted_until.is_some() {
+                if slot.state == BabyState::Crying {
+                    changes.extend(slot.enter(BabyState::Late, Reason::Muted));
+                }
+                muted.push(Event::WouldCry {
+                    id: slot.id,
+                    elapsed,
+                    metadata: slot.baby.metadata().map(EventMetadata),
+                });
+                continue;
+            }
+            let repeats = match self.dedup.as_mut() {
+                Some(dedup) => match dedup.admit(slot, now) {
+                    Some(repeats) => repeats,
+                    None => continue,
+                },
+                None => 0,
+            };
+            if let Some(rate_limit) = self.rate_limit.as_mut() {
+                if !rate_limit.admit(critical) {
+                    slot.starved += 1;
+                    continue;
+                }
+            }
+            slot.starved = 0;
+            let cry = CryContext {
+                id: slot.id,
+                elapsed,
+                repeats,
+                severity: slot.baby.severity(),
+                metadata: slot.baby.metadata(),
+            };
+            cries.push(cry);
+        }
+        for &id in &ids {
+            self.slots.refresh(id);
+        }
+        for event in changes.into_iter().chain(muted) {
+            self.emit(event);
+        }
+        if self.silent {
+            if let Some(health) = self.health.as_mut() {
+                health.cries += cries.len();
+            }
+            for cry in cries.drain(..) {
+                self.emit(Event::WouldCry {
+                    id: cry.id,
+                    elapsed: cry.elapsed,
+                    metadata: cry.metadata.map(EventMetadata),
+                });
+            }
+        }
+        // Critical cries are never batched.
+        let (critical, others): (Vec<_>, Vec<_>) = cries
+            .into_iter()
+            .partition(|cry| cry.severity == Severity::Critical);
+        for cry in critical {
+            self.cry(&cry, Reason::TimeoutElapsed)?;
+        }
+        match self.batch.as_mut() {
+            // Silent cries were all drained above, nothing to batch then.
+            Some((min, handler)) if !self.silent && others.len() >= (*min).max(1) => {
+                if let Some(health) = self.health.as_mut() {
+                    health.cries += others.len();
+                }
+                let result = catch(|| handler(&others));
+                self.report(result, ErrorSource::Batch)?;
+                for cry in others {
+                    self.record_cry(cry.id);
+                    self.transition(cry.id, BabyState::Crying, Reason::TimeoutElapsed);
+                }
+            }
+            _ => {
+                for cry in others {
+                    self.cry(&cry, Reason::TimeoutElapsed)?;
+                }
+            }
+        }
+        for id in unmuted {
+            self.emit(Event::Unmuted(id));
+        }
+        self.countdown(now);
+        self.report_health(now)
+    }
+
+    fn report_health(&mut self, now: Instant) -> BoxResult<()> {
+        let Some(health) = self.health.as_mut() else {
+            return Ok(());
+        };
+        if now.saturating_duration_since(health.last) < health.period {
+            return Ok(());
+        }
+        health.last = now;
+        let mut flappers: Vec<_> = self
+            .slots
+            .iter()
+            .filter(|slot| slot.flaps > 1)
+            .map(|slot| (slot.id, slot.flaps))
+            .collect();
+        flappers.sort_by_key(|&(id, flaps)| (Reverse(flaps), id));
+        flappers.truncate(3);
+        let mut overdue: Vec<_> = self
+            .slots
+            .iter()
+            .filter(|slot| slot.overdue > 0)
+            .map(|slot| slot.id)
+            .collect();
+        overdue.sort();
+        let mut starved: Vec<_> = self
+            .slots
+            .iter()
+            .filter(|slot| slot.starved > 0)
+            .map(|slot| (slot.id, slot.starved))
+            .collect();
+        starved.sort_by_key(|&(id, starved)| (Reverse(starved), id));
+        let summary = Health {
+            overdue,
+            cries: std::mem::take(&mut health.cries),
+            flappers,
+            starved,
+        };
+        self.slots.iter_mut().for_each(|slot| slot.flaps = 0);
+        let result = catch(|| (health.handler)(&summary));
+        self.report(result, ErrorSource::HealthReport)
+    }
+
+    /// Makes the baby cry for `reason`, along with the cradle-level actions for it.
+    fn cry(&mut self, cry: &CryContext, reason: Reason) -> BoxResult<()> {
+        let started = Instant::now();
+        #[cfg(feature = "chaos")]
+        thread::sleep(self.chaos.cry_delay());
+        if let Some(health) = self.health.as_mut() {
+            health.cries += 1;
+        }
+        let Some(slot) = self.slots.get_mut(cry.id) else {
+            return Ok(());
+        };
+        let baby = &mut slot.baby;
+        let source = ErrorSource::Cry(cry.id);
+        let default_cry = &mut self.default_cry;
+        let heard = self
+            .severity_actions
+            .iter()
+            .any(|(severity, _)| *severity == cry.severity);
+        let result = catch(|| {
+            if !baby.has_cry() {
+                match default_cry.as_mut() {
+                    Some(default_cry) => default_cry(cry),
+                    None if heard => Ok(()),
+                    None => Err(Box::new(Unheard(cry.id))),
+                }
+            } else if cry.repeats == 0 {
+                baby.cry(cry.elapsed)
+            } else {
+                baby.cry_repeated(cry.elapsed, cry.repeats)
+            }
+        });
+        self.report(result, source)?;
+        for i in 0..self.severity_actions.len() {
+            let (severity, action) = &mut self.severity_actions[i];
+            if *severity == cry.severity {
+                let result = catch(|| action(cry));
+                self.report(result, source)?;
+            }
+        }
+        self.spend(cry.id, started.elapsed());
+        self.record_cry(cry.id);
+        self.transition(cry.id, BabyState::Crying, reason);
+        Ok(())
+    }
+
+    fn record_cry(&mut self, id: BabyId) {
+        if let Some(cried) = self.cried.as_mut() {
+            cried.push(id);
+        }
+    }
+
+    /// Moves the baby to the state `to`, emitting an [`Event::StateChanged`].
+    fn transition(&mut self, id: BabyId, to: BabyState, reason: Reason) {
+        if let Some(event) = self
+            .slots
+            .get_mut(id)
+            .and_then(|slot| slot.enter(to, reason))
+        {
+            self.emit(event);
+        }
+    }
+
+    /// Accounts the time `took` by a cry of the baby, see [`CradleBuilder::cry_budget`].
+    fn spend(&mut self, id: BabyId, took: Duration) {
+        let Some(slot) = self.slots.get_mut(id) else {
+            return;
+        };
+        slot.cry_time += took;
+        let Some(budget) = self.cry_budget.as_ref() else {
+            return;
+        };
+        if took <= budget.budget {
+            slot.strikes = 0;
+            return;
+        }
+        slot.strikes += 1;
+        if slot.strikes >= budget.strikes {
+            slot.strikes = 0;
+            slot.muted_until = Some(later(Instant::now(), budget.mute));
+            self.slots.refresh(id);
+            self.emit(Event::OverBudget(id));
+        }
+    }
+
+    fn predict(&self, horizon: Duration, now: Instant) -> Vec<(BabyId, Instant)> {
+        if self.started_at.is_none() || self.muted {
+            return vec![];
+        }
+        let not_before = self.grace_end().map_or(now, |end| end.max(now));
+        // A horizon too far to fit in an `Instant` is unbounded.
+        let until = now.checked_add(horizon);
+        let mut cries: Vec<_> = self
+            .slots
+            .deadlines()
+            .map(|(id, due)| (id, due.max(not_before)))
+            .take_while(|&(_, due)| until.is_none_or(|until| due <= until))
+            .collect();
+        cries.sort_by_key(|&(id, due)| (due, id));
+        cries
+    }
+
+    /// The first deadline of a critical baby after `last`.
+    fn critical_deadline(&self, last: Instant) -> Option<Instant> {
+        if self.muted {
+            return None;
+        }
+        match self.grace_end().filter(|end| *end > last) {
+            // Every deadline falls after the grace period, overdue ones included.
+            Some(end) => self.slots.critical_deadline(None).map(|due| due.max(end)),
+            None => self.slots.critical_deadline(Some(last)),
+        }
+    }
+
+    fn countdown(&mut self, now: Instant) {
+        let grace_end = self.grace_end();
+        let Some(ticker) = self.ticker.as_mut() else {
+            return;
+        };
+        if ticker
+            .last
+            .is_some_and(|last| now.saturating_duration_since(last) < ticker.every)
+        {
+            return;
+        }
+        ticker.last = Some(now);
+        let countdowns: Vec<_> = ticker
+            .ids
+            .iter()
+            .filter_map(|&id| self.slots.get(id))
+            .filter_map(|slot| {
+                let remaining = slot.deadline(grace_end)?.saturating_duration_since(now);
+                Some(Event::Countdown {
+                    id: slot.id,
+                    remaining,
+                })
+            })
+            .collect();
+        for event in countdowns {
+            self.emit(event);
+        }
+    }
+
+    /// Says goodbye on graceful stop, every hook runs even if some fail.
+    fn goodbye(&mut self) -> BoxResult<()> {
+        let mut result = Ok(());
+        if self.silent {
+            return result;
+        }
+        for id in self.slots.ids() {
+            let Some(slot) = self.slots.get_mut(id) else {
+                continue;
+            };
+            let said = catch(|| slot.baby.on_shutdown());
+            result = result.and(self.report(said, ErrorSource::Shutdown(Some(id))));
+        }
+        if let Some(farewell) = self.farewell.as_mut() {
+            let said = catch(farewell);
+            result = result.and(self.report(said, ErrorSource::Shutdown(None)));
+        }
+        result
+    }
+
+    fn emit(&mut self, event: Event) {
+        if matches!(event, Event::StateChanged { .. }) && !self.state_events {
+            return;
+        }
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+        if self.journal.len() == JOURNAL {
+            self.journal.pop_front();
+        }
+        self.journal.push_back(event);
+        self.emitted += 1;
+    }
+
+    /// The events emitted since `cursor`, see [`Cradle::status_delta`](super::Cradle::status_delta).
+    fn delta(&self, cursor: usize) -> Delta {
+        let first = self.emitted - self.journal.len();
+        let skip = cursor.clamp(first, self.emitted) - first;
+        Delta {
+            cursor: self.emitted,
+            events: self.journal.iter().skip(skip).cloned().collect(),
+            truncated: cursor < first,
+        }
+    }
+
+    fn reset(&mut self, now: Instant) {
+        // A reset caught up late must not age babies put in since.
+        let mut changes = vec![];
+        self.slots.update_all(|slot| {
+            slot.since = slot.since.max(now);
+            slot.overdue = 0;
+            changes.extend(slot.soothe());
+        });
+        for event in changes {
+            self.emit(event);
+        }
+        self.status().reset_at = Some(now);
+        self.publish();
+    }
+
+    /// Applies the resets of [`Cradle::reset`](super::Cradle::reset) since the last call.
+    fn catch_up(&mut self) {
+        if self.started_at.is_none() {
+            return;
+        }
+        let seq = self.resets.seq.load(Ordering::Acquire);
+        let count = seq.wrapping_sub(self.seen);
+        if count == 0 {
+            return;
+        }
+        self.seen = seq;
+        let Some(at) = self.resets.last() else {
+            return;
+        };
+        self.reset_all(at, count);
+    }
+
+    /// Applies `count` resets of the whole cradle at `at`.
+    pub(super) fn reset_all(&mut self, at: Instant, count: usize) {
+        self.reset(at);
+        self.slots.update_all(|slot| slot.record_reset(at, count));
+        self.publish();
+    }
+
+    /// Hands an error to the error handler if any, otherwise returns it,
+    /// telling subscribers first about a callback which panicked.
+    fn report(&mut self, result: BoxResult<()>, source: ErrorSource) -> BoxResult<()> {
+        let Err(e) = result else {
+            return Ok(());
+        };
+        if e.is::<Panicked>() {
+            self.emit(Event::Panicked(source));
+        }
+        match self.on_error.as_mut() {
+            Some(on_error) => {
+                on_error(&*e, source);
+                Ok(())
+            }
+            None => Err(e),
+        }
+    }
+
+    fn status(&self) -> MutexGuard<'_, Status> {
+        Status::lock(&self.status)
+    }
+
+    /// Publishes the next deadline to the cradle handle,
+    /// telling subscribers if the status lock was recovered from a panic since the last time.
+    fn publish(&mut self) {
+        let next_deadline = if self.started_at.is_some() && !self.muted {
+            let due = self.slots.next_deadline();
+            due.map(|due| self.grace_end().map_or(due, |end| due.max(end)))
+        } else {
+            None
+        };
+        let poisoned = {
+            let mut status = self.status();
+            status.next_deadline = next_deadline;
+            std::mem::take(&mut status.poisoned)
+        };
+        if poisoned {
+            self.emit(Event::RegistryPoisoned);
+        }
+    }
+
+    /// When the [`CradleBuilder::grace`] period of the started cradle ends.
+    fn grace_end(&self) -> Option<Instant> {
+        self.started_at.map(|at| later(at, self.grace))
+    }
+
+    /// Makes a baby cry on demand, see [`Cradle::cry`](super::Cradle::cry).
+    fn cry_now(&mut self, id: BabyId, metadata: Option<Metadata>, now: Instant) -> BoxResult<()> {
+        let Some(slot) = self.slots.get(id) else {
+            return Ok(());
+        };
+        let muted = self.muted || slot.muted_until.is_some_and(|until| until > now);
+        let cry = CryContext {
+            id,
+            elapsed: slot.elapsed(now),
+            repeats: 0,
+            severity: slot.baby.severity(),
+            metadata: metadata.or_else(|| slot.baby.metadata()),
+        };
+        if self.silent || muted {
+            self.emit(Event::WouldCry {
+                id,
+                elapsed: cry.elapsed,
+                metadata: cry.metadata.map(EventMetadata),
+            });
+            return Ok(());
+        }
+        self.cry(&cry, Reason::ManualCry)
+    }
+
+    /// Handles a signal received at `now`.
+    pub(super) fn handle(&mut self, signal: Signal, now: Instant) -> BoxResult<()> {
+        self.catch_up();
+        match signal {
+            Signal::ResetFrom(id, source) => {
+                if let Some(slot) = self.slots.get_mut(id) {
+                    slot.last_seen.insert(source, now);
+                }
+            }
+            Signal::LastSeen(id, tx) => {
+                if let Some(slot) = self.slots.get(id) {
+                    let last_seen = slot
+                        .baby
+                        .sources()
+                        .iter()
+                        .map(|source| (source.name, slot.last_seen.get(source.name).copied()))
+                        .collect();
+                    let _ = tx.send(last_seen);
+                }
+            }
+            #[cfg(feature = "chaos")]
+            Signal::ResetBaby(_) if self.chaos.drops_reset() => {}
+            Signal::ResetBaby(id) => {
+                let soothed = self.slots.update(id, |slot| {
+                    slot.since = now;
+                    slot.overdue = 0;
+                    slot.record_reset(now, 1);
+                    slot.soothe()
+                });
+                if let Some(event) = soothed.flatten() {
+                    self.emit(event);
+                }
+            }
+            Signal::Put(id, baby) => self.slots.insert(Slot::new(id, baby, now)),
+            Signal::Mute => self.muted = true,
+            Signal::Unmute => self.muted = false,
+            Signal::MuteFor(id, until) => {
+                self.slots.update(id, |slot| slot.muted_until = Some(until));
+            }
+            Signal::Subscribe(subscriber) => self.subscribers.push(subscriber),
+            Signal::Take(id, tx) => {
+                if let Some(dedup) = self.dedup.as_mut() {
+                    dedup.forget(id);
+                }
+                if let Some(slot) = self.slots.remove(id) {
+                    let _ = tx.send(slot);
+                }
+            }
+            Signal::Insert(slot) => self.slots.insert(slot),
+            Signal::Remove(id) => {
+                if let Some(dedup) = self.dedup.as_mut() {
+                    dedup.forget(id);
+                }
+                self.slots.remove(id);
+            }
+            Signal::Cry(id) => return self.cry_now(id, None, now),
+            Signal::Panic(id, message, done) => {
+                let cried = self.cry_now(id, Some(Arc::new(message)), now);
+                let _ = done.send(());
+                return cried;
+            }
+            Signal::Predict(horizon, tx) => {
+                let _ = tx.send(self.predict(horizon, now));
+            }
+            Signal::CryTime(id, tx) => {
+                if let Some(slot) = self.slots.get(id) {
+                    let _ = tx.send(slot.cry_time);
+                }
+            }
+            Signal::Delta(cursor, tx) => {
+                let _ = tx.send(self.delta(cursor));
+            }
+            Signal::Start | Signal::Stop => {}
+        }
+        self.publish();
+        Ok(())
+    }
+
+    /// When to rock babies next, given the current tick happened at `now`.
+    fn next_tick(&self, now: Instant) -> Instant {
+        match self.align {
+            None => now + Duration::from_secs(1),
+            Some(boundary) => {
+                let boundary = boundary.as_secs().max(1) as u128 * 1_000_000_000;
+                let epoch = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos();
+                now + Duration::from_nanos((boundary - epoch % boundary) as u64)
+            }
+        }
+    }
+}
+
+/// How many of the latest events are kept for [`Cradle::status_delta`](super::Cradle::status_delta).
+const JOURNAL: usize = 1024;
+
+/// A baby without a cry of its own cried, but nothing could hear it.
+#[derive(Debug)]
+pub(super) struct Unheard(pub(super) BabyId);
+
+impl fmt::Display for Unheard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "baby {} cried without a cry of its own, a default cry nor a severity action",
+            self.0
+        )
+    }
+}
+
+impl Error for Unheard {}
+
+/// A baby lying in the cradle, with its own elapsed time.
+pub(super) struct Slot {
+    pub(super) id: BabyId,
+    baby: Box<dyn Baby + Send>,
+    /// When the baby was put in.
+    born: Instant,
+    since: Instant,
+    muted_until: Option<Instant>,
+    /// Consecutive ticks the baby wanted to cry.
+    overdue: usize,
+    /// How many times the baby started crying since the last health report.
+    flaps: usize,
+    /// Recent resets of a baby with a [`Baby::rate`].
+    resets: VecDeque<Instant>,
+    /// Banked resets, see [`Baby::bank`].
+    credit: usize,
+    /// Cries in a row dropped by the rate limit.
+    starved: usize,
+    /// When each of the [`Baby::sources`] last checked in.
+    last_seen: HashMap<String, Instant>,
+    /// The total time spent in the baby's cries.
+    cry_time: Duration,
+    /// Cries in a row over [`CradleBuilder::cry_budget`].
+    strikes: usize,
+    state: BabyState,
+}
+
+impl Slot {
+    fn new(id: BabyId, baby: Box<dyn Baby + Send>, now: Instant) -> Self {
+        Self {
+            id,
+            baby,
+            born: now,
+            since: now,
+            muted_until: None,
+            overdue: 0,
+            flaps: 0,
+            resets: VecDeque::new(),
+            credit: 0,
+            starved: 0,
+            last_seen: HashMap::new(),
+            cry_time: Duration::ZERO,
+            strikes: 0,
+            state: BabyState::Sleeping,
+        }
+    }
+
+    /// Whether the baby is [`Severity::Critical`].
+    pub(super) fn critical(&self) -> bool {
+        self.baby.severity() == Severity::Critical
+    }
+
+    /// The shard of the baby, before taking the modulo, see [`Baby::shard`].
+    pub(super) fn shard(&self) -> usize {
+        self.baby.shard().unwrap_or(self.id)
+    }
+
+    /// Moves the baby to the state `to`, returning the event telling so if it changed.
+    fn enter(&mut self, to: BabyState, reason: Reason) -> Option<Event> {
+        let from = std::mem::replace(&mut self.state, to);
+        (from != to).then_some(Event::StateChanged {
+            id: self.id,
+            from,
+            to,
+            reason,
+            metadata: self.baby.metadata().map(EventMetadata),
+        })
+    }
+
+    /// Moves every time the baby keeps `by` into the past, as if the clock jumped forward.
+    #[cfg(feature = "chaos")]
+    fn age(&mut self, by: Duration) {
+        let back = |at: Instant| at.checked_sub(by).unwrap_or(at);
+        self.born = back(self.born);
+        self.since = back(self.since);
+        self.muted_until = self.muted_until.map(back);
+        self.resets.iter_mut().for_each(|at| *at = back(*at));
+        self.last_seen.values_mut().for_each(|at| *at = back(*at));
+    }
+
+    /// Soothes a late or crying baby on reset.
+    fn soothe(&mut self) -> Option<Event> {
+        match self.state {
+            BabyState::Late | BabyState::Crying => {
+                self.enter(BabyState::Soothed, Reason::ResetReceived)
+            }
+            BabyState::Sleeping | BabyState::Soothed => None,
+        }
+    }
+
+    /// Records `count` resets for the baby's [`Baby::rate`] and [`Baby::bank`].
+    fn record_reset(&mut self, now: Instant, count: usize) {
+        // Only the latest resets up to the rate's minimum make a difference.
+        if let Some(rate) = self.baby.rate() {
+            let cap = rate.min.max(1);
+            self.resets.extend(std::iter::repeat_n(now, count.min(cap)));
+            let excess = self.resets.len().saturating_sub(cap);
+            self.resets.drain(..excess);
+        }
+        self.credit = (self.credit + count).min(self.baby.bank());
+    }
+
+    /// Whether fewer resets than `rate` arrived within its window,
+    /// once a whole window has passed since the baby began being watched.
+    fn below(&mut self, rate: Rate, now: Instant, started_at: Instant) -> bool {
+        let window = Duration::from_secs(rate.window as u64);
+        while self
+            .resets
+            .front()
+            .is_some_and(|at| now.saturating_duration_since(*at) >= window)
+        {
+            self.resets.pop_front();
+        }
+        let watched = now.saturating_duration_since(self.born.max(started_at));
+        watched >= window && self.resets.len() < rate.min
+    }
+
+    /// The elapsed time in seconds since the baby was put in or last reset.
+    fn elapsed(&self, now: Instant) -> usize {
+        now.saturating_duration_since(self.since).as_secs() as usize
+    }
+
+    /// Whether enough sources checked in within their interval,
+    /// counting from when the baby began being watched for those which never did.
+    fn quorate(&self, now: Instant, started_at: Instant) -> bool {
+        let sources = self.baby.sources();
+        let watched = self.born.max(started_at);
+        let alive = sources
+            .iter()
+            .filter(|source| {
+                let seen = self.last_seen.get(source.name).copied().unwrap_or(watched);
+                now.saturating_duration_since(seen) < Duration::from_secs(source.interval as u64)
+            })
+            .count();
+        alive >= self.baby.quorum().unwrap_or(sources.len())
+    }
+
+    /// When the baby is due to cry, considering its mute and banked resets,
+    /// but not before `not_before`, `None` if too far to tell.
+    pub(super) fn deadline(&self, not_before: Option<Instant>) -> Option<Instant> {
+        let timeout = self.baby.timeout()?.checked_mul(self.credit + 1)?;
+        let due = self
+            .since
+            .checked_add(Duration::from_secs(timeout as u64))?;
+        let due = self.muted_until.map_or(due, |until| due.max(until));
+        Some(not_before.map_or(due, |at| due.max(at)))
+    }
+}
+
+/// Limits cries per minute, see [`CradleBuilder::rate_limit`].
+struct RateLimit {
+    max: usize,
+    digest: Digest,
+    window: Instant,
+    cries: usize,
+    dropped: usize,
+}
+
+impl RateLimit {
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn new(max: usize, digest: Digest) -> Self {
+        Self {
+            max,
+            digest,
+            window: Instant::now(),
+            cries: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Starts a new window once the current one is over,
+    /// returning how many cries were dropped for the digest, if any.
+    fn roll(&mut self, now: Instant) -> Option<usize> {
+        if now.saturating_duration_since(self.window) < Self::WINDOW {
+            return None;
+        }
+        self.window = now;
+        self.cries = 0;
+        match std::mem::take(&mut self.dropped) {
+            0 => None,
+            dropped => Some(dropped),
+        }
+    }
+
+    /// Whether one more cry fits in the current window, `force` for critical cries
+    /// which always go through, leaving less room for others.
+    fn admit(&mut self, force: bool) -> bool {
+        if force || self.cries < self.max {
+            self.cries += 1;
+            true
+        } else {
+            self.dropped += 1;
+            false
+        }
+    }
+}
+
+/// Collapses cries within a window, see [`CradleBuilder::dedup`].
+struct Dedup {
+    window: Duration,
+    /// When the window started, the last cry, and how many were collapsed.
+    entries: HashMap<DedupKey, (Instant, Instant, usize)>,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum DedupKey {
+    Baby(BabyId),
+    Key(String),
+}
+
+impl Dedup {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of cries collapsed before this one, or `None` if it is collapsed.
+    fn admit(&mut self, slot: &Slot, now: Instant) -> Option<usize> {
+        let key = match slot.baby.dedup_key() {
+            Some(key) => DedupKey::Key(key.to_owned()),
+            None => DedupKey::Baby(slot.id),
+        };
+        match self.entries.get_mut(&key) {
+            Some((since, last, repeats)) if now.saturating_duration_since(*since) < self.window => {
+                *last = now;
+                *repeats += 1;
+                None
+            }
+            Some((since, last, repeats)) => {
+                *since = now;
+                *last = now;
+                Some(std::mem::take(repeats))
+            }
+            None => {
+                self.entries.insert(key, (now, now, 0));
+                Some(0)
+            }
+        }
+    }
+
+    /// Drops entries without a cry for a whole window, their collapsed cries with them,
+    /// so that they are not reported by an unrelated cry much later.
+    fn prune(&mut self, now: Instant) {
+        self.entries
+            .retain(|_, (_, last, _)| now.saturating_duration_since(*last) < self.window);
+    }
+
+    /// Drops the entry of a baby gone from the cradle.
+    fn forget(&mut self, id: BabyId) {
+        self.entries.remove(&DedupKey::Baby(id));
+    }
+}
+
+/// Mutes babies with slow cries, see [`CradleBuilder::cry_budget`].
+struct CryBudget {
+    budget: Duration,
+    strikes: usize,
+    mute: Duration,
+}
+
+/// Emits countdowns, see [`CradleBuilder::ticker`].
+struct Ticker {
+    every: Duration,
+    ids: Vec<BabyId>,
+    last: Option<Instant>,
+}
+
+/// Reports health periodically, see [`CradleBuilder::health_report`].
+struct Reporter {
+    period: Duration,
+    handler: HealthReport,
+    last: Instant,
+    cries: usize,
+}